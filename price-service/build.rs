@@ -0,0 +1,13 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+    // Descriptor set is embedded for gRPC server reflection
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("price_service_descriptor.bin"))
+        .compile(&["proto/price_service.proto"], &["proto"])?;
+
+    Ok(())
+}
@@ -1,60 +1,152 @@
-use std::collections::HashMap;
+// tonic::Status is large, but it is the error type the generated service
+// traits are built around
+#![allow(clippy::result_large_err)]
+
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use futures::Stream;
-use futures::stream;
+use futures::{Stream, StreamExt};
 use tokio::time;
 use tokio::sync::mpsc;
+use tokio::task::JoinSet;
 
-use reqwest;
 use serde::{Deserialize, Serialize};
 use tonic::{Request, Response, Status};
 
+// Types and service traits generated from proto/price_service.proto
+pub mod proto {
+    tonic::include_proto!("priceservice");
+}
+
+use proto::price_service_server::PriceService;
+use proto::{PriceRequest, PriceResponse, PriceUpdate, ServiceInfo, ServiceInfoRequest, SubscriptionRequest};
+
+// Encoded proto descriptors, served through gRPC reflection
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("price_service_descriptor");
+
+// Reflection service so grpcurl and clients can discover the schema
+pub fn reflection_service() -> Result<
+    tonic_reflection::server::ServerReflectionServer<impl tonic_reflection::server::ServerReflection>,
+    tonic_reflection::server::Error,
+> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build()
+}
+
 // Price fetching structs
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct TokenPrice {
-    symbol: String,
-    price: f64,
-    source: String,
+pub struct TokenPrice {
+    pub symbol: String,
+    pub price: f64,
+    pub source: String,
 }
 
-#[derive(Default)]
-pub struct PriceServiceImpl {
-    client: reqwest::Client,
+// Prices split by whether any provider could resolve the requested token
+#[derive(Debug, Clone, Default)]
+pub struct PriceLookup {
+    pub resolved: HashMap<String, TokenPrice>,
+    pub unresolved: Vec<String>,
 }
 
-impl PriceServiceImpl {
-    // Providers for price fetching
-    const PRICE_PROVIDERS: &'static [(&'static str, &'static str)] = &[
-        ("binance", "https://api.binance.com/api/v3/ticker/price"),
-        ("coingecko", "https://api.coingecko.com/api/v3/simple/price"),
-    ];
-
-    async fn fetch_prices(&self, tokens: &[String]) -> Result<HashMap<String, TokenPrice>, Box<dyn std::error::Error>> {
-        let mut prices = HashMap::new();
-
-        for (source, url) in Self::PRICE_PROVIDERS {
-            match self.fetch_provider_prices(url, tokens, source).await {
-                Ok(provider_prices) => {
-                    prices.extend(provider_prices);
-                }
-                Err(e) => {
-                    eprintln!("Error fetching prices from {}: {}", source, e);
+impl PriceLookup {
+    pub fn new(requested: &[String], mut prices: HashMap<String, TokenPrice>) -> Self {
+        let mut lookup = PriceLookup::default();
+
+        for token in requested {
+            let symbol = token.to_uppercase();
+            if lookup.resolved.contains_key(&symbol) || lookup.unresolved.contains(token) {
+                continue;
+            }
+
+            match prices.remove(&symbol) {
+                Some(price) => {
+                    lookup.resolved.insert(symbol, price);
                 }
+                None => lookup.unresolved.push(token.clone()),
             }
         }
 
-        Ok(prices)
+        lookup
     }
+}
 
-    async fn fetch_provider_prices(
-        &self,
-        url: &str,
-        tokens: &[String],
-        source: &str
-    ) -> Result<HashMap<String, TokenPrice>, reqwest::Error> {
-        let response = self.client.get(url)
+// Failure reported by a price provider
+#[derive(Debug)]
+pub enum ProviderError {
+    Http(reqwest::Error),
+    Unavailable(String),
+}
+
+impl std::fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::Http(e) => write!(f, "HTTP error: {}", e),
+            ProviderError::Unavailable(reason) => write!(f, "Provider unavailable: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+impl From<reqwest::Error> for ProviderError {
+    fn from(e: reqwest::Error) -> Self {
+        ProviderError::Http(e)
+    }
+}
+
+/// A source of token prices. Implement this to plug in providers that
+/// need auth, POST bodies or streaming transports.
+#[tonic::async_trait]
+pub trait PriceProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Base trust weight; scaled by the provider's rolling reliability score.
+    fn reliability(&self) -> f64 {
+        1.0
+    }
+
+    /// Prices keyed by upper-case symbol. Tokens the provider doesn't know
+    /// are simply left out.
+    async fn fetch(&self, tokens: &[String]) -> Result<HashMap<String, TokenPrice>, ProviderError>;
+}
+
+// Configuration for a price provider
+#[derive(Debug, Clone)]
+pub struct ProviderConfig {
+    pub name: String,
+    pub url: String,
+    // Base trust weight; scaled by the provider's rolling reliability score
+    pub reliability: f64,
+}
+
+// Provider answering `GET url?symbols=A,B` with a `{symbol: price}` JSON map
+pub struct HttpPriceProvider {
+    config: ProviderConfig,
+    client: reqwest::Client,
+}
+
+impl HttpPriceProvider {
+    pub fn new(config: ProviderConfig, client: reqwest::Client) -> Self {
+        HttpPriceProvider { config, client }
+    }
+}
+
+#[tonic::async_trait]
+impl PriceProvider for HttpPriceProvider {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn reliability(&self) -> f64 {
+        self.config.reliability
+    }
+
+    async fn fetch(&self, tokens: &[String]) -> Result<HashMap<String, TokenPrice>, ProviderError> {
+        let response = self.client.get(&self.config.url)
             .query(&[("symbols", tokens.join(","))])
             .send()
             .await?
@@ -65,12 +157,439 @@ impl PriceServiceImpl {
             (symbol.to_uppercase(), TokenPrice {
                 symbol: symbol.to_uppercase(),
                 price,
-                source: source.to_string(),
+                source: self.config.name.clone(),
             })
         }).collect())
     }
 }
 
+// Timestamped price samples per token, oldest first
+type PriceHistory = HashMap<String, VecDeque<(Instant, f64)>>;
+
+// Aggregated prices split by the anomaly guard, each judged once
+struct ScreenedPrices {
+    accepted: HashMap<String, TokenPrice>,
+    withheld: HashMap<String, TokenPrice>,
+}
+
+// Cheap handle: clones share providers, reliability scores and the set
+// of running subscription tasks
+#[derive(Clone)]
+pub struct PriceServiceImpl {
+    providers: Arc<Vec<Box<dyn PriceProvider>>>,
+    // Rolling reliability score per provider (1.0 = never an outlier)
+    reliability_scores: Arc<Mutex<HashMap<String, f64>>>,
+    // Limit for a single provider call
+    request_timeout: Duration,
+    // Limit for the whole fan-out across providers
+    fetch_deadline: Duration,
+    // Streaming subscription tasks, aborted on shutdown
+    subscriptions: Arc<Mutex<JoinSet<()>>>,
+    shut_down: Arc<AtomicBool>,
+    // Recent aggregated prices per token, oldest first, for TWAP queries
+    price_history: Arc<Mutex<PriceHistory>>,
+    // How far back price samples are retained
+    history_retention: Duration,
+    // Relative deviation from the TWAP beyond which a price is withheld
+    anomaly_threshold: f64,
+    // TWAP window new prices are compared against
+    anomaly_window: Duration,
+    // Consistent anomalous readings after which the move is taken as genuine
+    anomaly_confirmations: usize,
+    // Withheld samples per token since its last accepted price, oldest first
+    withheld_history: Arc<Mutex<PriceHistory>>,
+}
+
+impl Default for PriceServiceImpl {
+    fn default() -> Self {
+        Self::with_providers(Self::default_providers())
+    }
+}
+
+impl PriceServiceImpl {
+    const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+    const DEFAULT_FETCH_DEADLINE: Duration = Duration::from_secs(10);
+    const DEFAULT_HISTORY_RETENTION: Duration = Duration::from_secs(60 * 60);
+    const DEFAULT_ANOMALY_THRESHOLD: f64 = 0.5;
+    const DEFAULT_ANOMALY_WINDOW: Duration = Duration::from_secs(5 * 60);
+    const DEFAULT_ANOMALY_CONFIRMATIONS: usize = 3;
+
+    // Relative deviation from the weighted median that counts as an outlier
+    const OUTLIER_THRESHOLD: f64 = 0.02;
+    // Smoothing factor for the rolling reliability score
+    const RELIABILITY_ALPHA: f64 = 0.2;
+
+    // Providers for price fetching
+    fn default_providers() -> Vec<ProviderConfig> {
+        vec![
+            ProviderConfig {
+                name: "binance".to_string(),
+                url: "https://api.binance.com/api/v3/ticker/price".to_string(),
+                reliability: 1.0,
+            },
+            ProviderConfig {
+                name: "coingecko".to_string(),
+                url: "https://api.coingecko.com/api/v3/simple/price".to_string(),
+                reliability: 0.8,
+            },
+        ]
+    }
+
+    // HTTP providers sharing one connection pool
+    pub fn with_providers(providers: Vec<ProviderConfig>) -> Self {
+        let client = reqwest::Client::new();
+        Self::with_price_providers(providers.into_iter()
+            .map(|config| Box::new(HttpPriceProvider::new(config, client.clone())) as Box<dyn PriceProvider>)
+            .collect())
+    }
+
+    pub fn with_price_providers(providers: Vec<Box<dyn PriceProvider>>) -> Self {
+        PriceServiceImpl {
+            providers: Arc::new(providers),
+            reliability_scores: Arc::new(Mutex::new(HashMap::new())),
+            request_timeout: Self::DEFAULT_REQUEST_TIMEOUT,
+            fetch_deadline: Self::DEFAULT_FETCH_DEADLINE,
+            subscriptions: Arc::new(Mutex::new(JoinSet::new())),
+            shut_down: Arc::new(AtomicBool::new(false)),
+            price_history: Arc::new(Mutex::new(HashMap::new())),
+            history_retention: Self::DEFAULT_HISTORY_RETENTION,
+            anomaly_threshold: Self::DEFAULT_ANOMALY_THRESHOLD,
+            anomaly_window: Self::DEFAULT_ANOMALY_WINDOW,
+            anomaly_confirmations: Self::DEFAULT_ANOMALY_CONFIRMATIONS,
+            withheld_history: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_fetch_deadline(mut self, deadline: Duration) -> Self {
+        self.fetch_deadline = deadline;
+        self
+    }
+
+    pub fn with_history_retention(mut self, retention: Duration) -> Self {
+        self.history_retention = retention;
+        self
+    }
+
+    /// Withhold prices that deviate from the trailing `window` TWAP by more
+    /// than `threshold` (relative), e.g. 0.5 for ±50%
+    pub fn with_anomaly_guard(mut self, threshold: f64, window: Duration) -> Self {
+        self.anomaly_threshold = threshold;
+        self.anomaly_window = window;
+        self
+    }
+
+    /// Accept a move once this many consecutive withheld prices agree with
+    /// each other, so the guard can't stay pinned to a stale TWAP
+    pub fn with_anomaly_confirmations(mut self, confirmations: usize) -> Self {
+        self.anomaly_confirmations = confirmations;
+        self
+    }
+
+    /// Query all providers concurrently. Providers that error or miss their
+    /// timeout are reported and left out of the aggregate; if none of them
+    /// answered, the fetch fails rather than returning an empty map.
+    /// Prices the anomaly guard withholds are still returned here but are
+    /// kept out of the TWAP history.
+    pub async fn fetch_prices(&self, tokens: &[String]) -> Result<HashMap<String, TokenPrice>, Box<dyn std::error::Error + Send + Sync>> {
+        let screened = self.fetch_screened(tokens).await?;
+        let mut prices = screened.accepted;
+        prices.extend(screened.withheld);
+        Ok(prices)
+    }
+
+    async fn fetch_screened(&self, tokens: &[String]) -> Result<ScreenedPrices, Box<dyn std::error::Error + Send + Sync>> {
+        let deadline = time::Instant::now() + self.fetch_deadline;
+
+        let fetches = self.providers.iter().map(|provider| async move {
+            let provider_deadline = deadline.min(time::Instant::now() + self.request_timeout);
+            let result = time::timeout_at(provider_deadline, provider.fetch(tokens)).await;
+            (provider, result)
+        });
+
+        let mut quotes = Vec::new();
+        for (provider, result) in futures::future::join_all(fetches).await {
+            match result {
+                Ok(Ok(provider_prices)) => {
+                    let prices = provider_prices.into_iter()
+                        .map(|(symbol, price)| (symbol, price.price))
+                        .collect();
+                    quotes.push((provider.name(), prices));
+                }
+                Ok(Err(e)) => {
+                    eprintln!("Error fetching prices from {}: {}", provider.name(), e);
+                }
+                Err(_) => {
+                    eprintln!("Timed out fetching prices from {}", provider.name());
+                }
+            }
+        }
+
+        if quotes.is_empty() && !self.providers.is_empty() {
+            return Err("No price provider answered".into());
+        }
+
+        let now = Instant::now();
+        let mut screened = ScreenedPrices { accepted: HashMap::new(), withheld: HashMap::new() };
+        for (symbol, price) in self.aggregate_quotes(&quotes) {
+            if self.admit_price(&symbol, now, price.price) {
+                screened.accepted.insert(symbol, price);
+            } else {
+                screened.withheld.insert(symbol, price);
+            }
+        }
+
+        Ok(screened)
+    }
+
+    // Runs the anomaly guard on a fresh price and records it if accepted.
+    // Anomalous prices stay out of the history so they can't drag the TWAP
+    // they are judged against toward themselves; once enough consecutive
+    // ones agree, the market has really moved and they replace it.
+    fn admit_price(&self, token: &str, at: Instant, price: f64) -> bool {
+        let mut withheld_history = self.withheld_history.lock().unwrap();
+        if !self.is_anomalous(token, price) {
+            withheld_history.remove(token);
+            self.record_price(token, at, price);
+            return true;
+        }
+
+        let withheld = withheld_history.entry(token.to_string()).or_default();
+        // A reading that disagrees with the withheld run starts a new one
+        if let Some(&(_, first)) = withheld.front() {
+            if (price - first).abs() / first > self.anomaly_threshold {
+                withheld.clear();
+            }
+        }
+        withheld.push_back((at, price));
+        if withheld.len() < self.anomaly_confirmations {
+            return false;
+        }
+
+        let confirmed = withheld_history.remove(token).unwrap_or_default();
+        self.price_history.lock().unwrap().insert(token.to_string(), confirmed);
+        true
+    }
+
+    /// Add a price sample to the token's rolling history. Samples older than
+    /// the retention period are dropped, except the newest of them, which
+    /// still sets the price at the start of the window.
+    pub fn record_price(&self, token: &str, at: Instant, price: f64) {
+        if !price.is_finite() {
+            return;
+        }
+
+        let mut history = self.price_history.lock().unwrap();
+        let samples = history.entry(token.to_string()).or_default();
+        let position = samples.partition_point(|(timestamp, _)| *timestamp <= at);
+        samples.insert(position, (at, price));
+
+        if let Some(cutoff) = Instant::now().checked_sub(self.history_retention) {
+            while samples.len() > 1 && samples[1].0 <= cutoff {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Time-weighted average price over the trailing `window`. Each sample
+    /// holds until the next one; `None` until the window spans at least two
+    /// samples.
+    pub fn get_twap(&self, token: &str, window: Duration) -> Option<f64> {
+        let now = Instant::now();
+        let start = now.checked_sub(window)?;
+
+        let history = self.price_history.lock().unwrap();
+        let samples = history.get(token)?;
+
+        // The last sample at or before `start` carries into the window
+        let first = samples.partition_point(|(timestamp, _)| *timestamp <= start).saturating_sub(1);
+        let in_window: Vec<(Instant, f64)> = samples.iter()
+            .skip(first)
+            .filter(|(timestamp, _)| *timestamp <= now)
+            .copied()
+            .collect();
+        if in_window.len() < 2 {
+            return None;
+        }
+
+        let mut weighted_sum = 0.0;
+        let mut covered = Duration::ZERO;
+        for (i, (timestamp, price)) in in_window.iter().enumerate() {
+            let from = (*timestamp).max(start);
+            let until = in_window.get(i + 1).map(|(next, _)| *next).unwrap_or(now);
+            let held = until.saturating_duration_since(from);
+            weighted_sum += price * held.as_secs_f64();
+            covered += held;
+        }
+
+        if covered.is_zero() {
+            return None;
+        }
+        Some(weighted_sum / covered.as_secs_f64())
+    }
+
+    /// Whether `price` deviates from the token's TWAP by more than the
+    /// anomaly threshold. Tokens without enough history are never anomalous.
+    pub fn is_anomalous(&self, token: &str, price: f64) -> bool {
+        match self.get_twap(token, self.anomaly_window) {
+            Some(twap) if twap > 0.0 => (price - twap).abs() / twap > self.anomaly_threshold,
+            _ => false,
+        }
+    }
+
+    /// Start a task that pushes fresh prices for `tokens` every `interval`.
+    /// Prices failing the anomaly guard are withheld until enough consistent
+    /// readings confirm the move. The task is tracked so
+    /// `shutdown` can stop it; after shutdown new subscriptions are rejected.
+    pub fn spawn_subscription(
+        &self,
+        tokens: Vec<String>,
+        interval: Duration
+    ) -> Result<mpsc::Receiver<TokenPrice>, Status> {
+        // Checked under the lock so a racing shutdown can't miss this task
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if self.shut_down.load(Ordering::SeqCst) {
+            return Err(Status::unavailable("Price service is shutting down"));
+        }
+
+        // Drop handles of subscriptions whose clients went away
+        while subscriptions.try_join_next().is_some() {}
+
+        let (tx, rx) = mpsc::channel(100);
+        let service = self.clone();
+        subscriptions.spawn(async move {
+            let mut ticker = time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                // Skip the tick if every provider failed
+                if let Ok(screened) = service.fetch_screened(&tokens).await {
+                    for (symbol, price) in screened.withheld {
+                        eprintln!("Withholding anomalous price {} for {}", price.price, symbol);
+                    }
+                    for price in screened.accepted.into_values() {
+                        if tx.send(price).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Abort every subscription task and wait for them to finish.
+    pub async fn shutdown(&self) {
+        let mut subscriptions = {
+            let mut guard = self.subscriptions.lock().unwrap();
+            self.shut_down.store(true, Ordering::SeqCst);
+            std::mem::take(&mut *guard)
+        };
+
+        subscriptions.abort_all();
+        while subscriptions.join_next().await.is_some() {}
+    }
+
+    /// Number of subscription tasks still running.
+    pub fn active_subscriptions(&self) -> usize {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        while subscriptions.try_join_next().is_some() {}
+        subscriptions.len()
+    }
+
+    /// Combine per-provider quotes into one weighted-median price per token,
+    /// updating each provider's rolling reliability score along the way.
+    pub fn aggregate_quotes(&self, quotes: &[(&str, HashMap<String, f64>)]) -> HashMap<String, TokenPrice> {
+        let mut by_token: HashMap<&str, Vec<(&str, f64)>> = HashMap::new();
+        for (provider, prices) in quotes {
+            for (symbol, price) in prices {
+                by_token.entry(symbol.as_str()).or_default().push((provider, *price));
+            }
+        }
+
+        let mut scores = self.reliability_scores.lock().unwrap();
+        let mut aggregated = HashMap::new();
+
+        for (symbol, samples) in by_token {
+            let weighted: Vec<(f64, f64)> = samples.iter()
+                .map(|(provider, price)| (*price, self.weight_for(&scores, provider)))
+                .collect();
+
+            let median = match Self::weighted_median(&weighted) {
+                Some(median) => median,
+                None => continue,
+            };
+
+            // Only score providers when there is something to compare against
+            if samples.len() > 1 {
+                for (provider, price) in &samples {
+                    let deviation = ((price - median) / median).abs();
+                    let observation = if deviation > Self::OUTLIER_THRESHOLD { 0.0 } else { 1.0 };
+                    let score = scores.entry(provider.to_string()).or_insert(1.0);
+                    *score += Self::RELIABILITY_ALPHA * (observation - *score);
+                }
+            }
+
+            let sources: Vec<&str> = samples.iter().map(|(provider, _)| *provider).collect();
+            aggregated.insert(symbol.to_string(), TokenPrice {
+                symbol: symbol.to_string(),
+                price: median,
+                source: sources.join(","),
+            });
+        }
+
+        aggregated
+    }
+
+    /// Names of the configured price providers.
+    pub fn supported_providers(&self) -> Vec<String> {
+        self.providers.iter()
+            .map(|provider| provider.name().to_string())
+            .collect()
+    }
+
+    /// Current effective weight of a provider: its configured reliability
+    /// scaled by its rolling score.
+    pub fn provider_weight(&self, provider: &str) -> f64 {
+        let scores = self.reliability_scores.lock().unwrap();
+        self.weight_for(&scores, provider)
+    }
+
+    fn weight_for(&self, scores: &HashMap<String, f64>, provider: &str) -> f64 {
+        let base = self.providers.iter()
+            .find(|candidate| candidate.name() == provider)
+            .map(|candidate| candidate.reliability())
+            .unwrap_or(1.0);
+
+        base * scores.get(provider).copied().unwrap_or(1.0)
+    }
+
+    fn weighted_median(samples: &[(f64, f64)]) -> Option<f64> {
+        let mut sorted: Vec<(f64, f64)> = samples.iter()
+            .copied()
+            .filter(|(price, weight)| price.is_finite() && *weight > 0.0)
+            .collect();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let total_weight: f64 = sorted.iter().map(|(_, weight)| weight).sum();
+        let mut cumulative = 0.0;
+        for (price, weight) in &sorted {
+            cumulative += weight;
+            if cumulative >= total_weight / 2.0 {
+                return Some(*price);
+            }
+        }
+
+        None
+    }
+}
+
 // gRPC Service Implementation
 #[tonic::async_trait]
 impl PriceService for PriceServiceImpl {
@@ -83,11 +602,13 @@ impl PriceService for PriceServiceImpl {
         let prices = self.fetch_prices(&tokens)
             .await
             .map_err(|_| Status::internal("Price fetching failed"))?;
+        let lookup = PriceLookup::new(&tokens, prices);
 
         let response = PriceResponse {
-            prices: prices.into_iter()
+            resolved: lookup.resolved.into_iter()
                 .map(|(token, price)| (token, price.into()))
                 .collect(),
+            unresolved: lookup.unresolved,
             timestamp: chrono::Utc::now().timestamp(),
         };
 
@@ -102,51 +623,34 @@ impl PriceService for PriceServiceImpl {
         request: Request<SubscriptionRequest>
     ) -> Result<Response<Self::SubscribePriceUpdatesStream>, Status> {
         let subscription = request.into_inner();
-        let (tx, rx) = mpsc::channel(100);
+        let interval = Duration::from_millis(subscription.update_interval_ms.max(1) as u64);
 
-        // Clone tokens for move into async block
-        let tokens = subscription.tokens.clone();
-        let interval_ms = subscription.update_interval_ms;
-
-        tokio::spawn(async move {
-            let mut interval = time::interval(
-                Duration::from_millis(interval_ms as u64)
-            );
-
-            loop {
-                interval.tick().await;
-
-                match self.fetch_prices(&tokens).await {
-                    Ok(prices) => {
-                        for (token, price_data) in prices {
-                            let update = PriceUpdate {
-                                token,
-                                price: price_data.price,
-                                timestamp: chrono::Utc::now().timestamp(),
-                            };
-
-                            if tx.send(Ok(update)).await.is_err() {
-                                break;
-                            }
-                        }
-                    }
-                    Err(_) => {
-                        // Optional: send error or skip
-                    }
-                }
-            }
-        });
+        let rx = self.spawn_subscription(subscription.tokens, interval)?;
 
         // Convert channel receiver to stream
-        let stream = stream::wrappers::ReceiverStream::new(rx);
+        let stream = tokio_stream::wrappers::ReceiverStream::new(rx).map(|price| Ok(PriceUpdate {
+            token: price.symbol,
+            price: price.price,
+            timestamp: chrono::Utc::now().timestamp(),
+        }));
         Ok(Response::new(Box::pin(stream) as Self::SubscribePriceUpdatesStream))
     }
+
+    async fn get_service_info(
+        &self,
+        _request: Request<ServiceInfoRequest>
+    ) -> Result<Response<ServiceInfo>, Status> {
+        Ok(Response::new(ServiceInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            providers: self.supported_providers(),
+        }))
+    }
 }
 
 // Conversion for protobuf compatibility
-impl From<TokenPrice> for TokenPrice {
+impl From<TokenPrice> for proto::TokenPrice {
     fn from(price: TokenPrice) -> Self {
-        TokenPrice {
+        proto::TokenPrice {
             price: price.price,
             source: price.source,
         }
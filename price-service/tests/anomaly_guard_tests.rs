@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bnbmarket_price_service::{PriceProvider, PriceServiceImpl, ProviderError, TokenPrice};
+
+// Replays a fixed sequence of BNB prices, one per fetch, then repeats the last
+struct ScriptedProvider {
+    prices: Mutex<Vec<f64>>,
+}
+
+#[tonic::async_trait]
+impl PriceProvider for ScriptedProvider {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    async fn fetch(&self, _tokens: &[String]) -> Result<HashMap<String, TokenPrice>, ProviderError> {
+        let price = {
+            let mut prices = self.prices.lock().unwrap();
+            if prices.len() > 1 { prices.remove(0) } else { prices[0] }
+        };
+
+        let symbol = "BNB".to_string();
+        Ok(HashMap::from([(symbol.clone(), TokenPrice { symbol, price, source: self.name().to_string() })]))
+    }
+}
+
+fn service_with_history(prices: Vec<f64>) -> PriceServiceImpl {
+    let provider = ScriptedProvider { prices: Mutex::new(prices) };
+    let service = PriceServiceImpl::with_price_providers(vec![Box::new(provider)])
+        .with_anomaly_guard(0.5, Duration::from_secs(60));
+
+    let now = Instant::now();
+    for seconds_ago in [50, 30, 10] {
+        service.record_price("BNB", now - Duration::from_secs(seconds_ago), 300.0);
+    }
+    service
+}
+
+#[tokio::test]
+async fn test_price_spike_is_withheld_from_stream() {
+    let service = service_with_history(vec![305.0, 30_000.0, 310.0]);
+
+    let mut rx = service.spawn_subscription(vec!["BNB".to_string()], Duration::from_millis(10)).unwrap();
+
+    let first = rx.recv().await.unwrap();
+    let second = rx.recv().await.unwrap();
+    assert_eq!(first.price, 305.0);
+    assert_eq!(second.price, 310.0, "The 100x spike should not reach subscribers");
+
+    // Nor does it move the TWAP later prices are judged against
+    let twap = service.get_twap("BNB", Duration::from_secs(60)).unwrap();
+    assert!(twap < 400.0, "TWAP {} was dragged by the spike", twap);
+
+    service.shutdown().await;
+}
+
+#[test]
+fn test_guard_needs_history_to_judge() {
+    let service = PriceServiceImpl::with_price_providers(Vec::new());
+
+    assert!(!service.is_anomalous("BNB", 1_000_000.0));
+
+    let service = service_with_history(vec![300.0]);
+    assert!(service.is_anomalous("BNB", 30_000.0));
+    assert!(service.is_anomalous("BNB", 3.0));
+    assert!(!service.is_anomalous("BNB", 330.0));
+}
+
+#[tokio::test]
+async fn test_sustained_move_is_accepted_after_confirmations() {
+    let service = service_with_history(vec![600.0, 610.0, 605.0, 620.0]).with_anomaly_confirmations(3);
+
+    let mut rx = service.spawn_subscription(vec!["BNB".to_string()], Duration::from_millis(10)).unwrap();
+
+    // The first two readings at the new level are withheld, the third
+    // confirms the move and later prices are judged against it
+    let first = rx.recv().await.unwrap();
+    let second = rx.recv().await.unwrap();
+    assert_eq!(first.price, 605.0);
+    assert_eq!(second.price, 620.0);
+
+    let twap = service.get_twap("BNB", Duration::from_secs(60)).unwrap();
+    assert!(twap > 550.0, "TWAP {} still pinned to the old level", twap);
+
+    service.shutdown().await;
+}
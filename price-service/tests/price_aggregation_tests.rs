@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use bnbmarket_price_service::PriceServiceImpl;
+
+fn quotes(binance: f64, coingecko: f64) -> Vec<(&'static str, HashMap<String, f64>)> {
+    vec![
+        ("binance", HashMap::from([("BNB".to_string(), binance)])),
+        ("coingecko", HashMap::from([("BNB".to_string(), coingecko)])),
+    ]
+}
+
+#[test]
+fn test_repeated_outlier_weight_decays() {
+    let service = PriceServiceImpl::default();
+    let initial_weight = service.provider_weight("coingecko");
+
+    let mut previous = initial_weight;
+    for _ in 0..5 {
+        let prices = service.aggregate_quotes(&quotes(300.0, 450.0));
+        assert_eq!(prices["BNB"].price, 300.0, "Trusted provider should dominate the median");
+
+        let weight = service.provider_weight("coingecko");
+        assert!(weight < previous, "Outlier weight should keep decaying");
+        previous = weight;
+    }
+
+    assert!(previous < initial_weight * 0.5);
+    assert_eq!(service.provider_weight("binance"), 1.0);
+}
+
+#[test]
+fn test_agreeing_providers_keep_full_weight() {
+    let service = PriceServiceImpl::default();
+
+    service.aggregate_quotes(&quotes(300.0, 300.5));
+
+    assert_eq!(service.provider_weight("binance"), 1.0);
+    assert_eq!(service.provider_weight("coingecko"), 0.8);
+}
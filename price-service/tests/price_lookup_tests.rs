@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use bnbmarket_price_service::{PriceLookup, TokenPrice};
+
+fn price(symbol: &str, price: f64) -> (String, TokenPrice) {
+    (symbol.to_string(), TokenPrice {
+        symbol: symbol.to_string(),
+        price,
+        source: "binance".to_string(),
+    })
+}
+
+#[test]
+fn test_lookup_splits_supported_and_unsupported_tokens() {
+    let requested = vec!["BNB".to_string(), "eth".to_string(), "NOTACOIN".to_string()];
+    let prices = HashMap::from([price("BNB", 300.0), price("ETH", 2000.0)]);
+
+    let lookup = PriceLookup::new(&requested, prices);
+
+    assert_eq!(lookup.resolved.len(), 2);
+    assert_eq!(lookup.resolved["BNB"].price, 300.0);
+    assert_eq!(lookup.resolved["ETH"].price, 2000.0);
+    assert_eq!(lookup.unresolved, vec!["NOTACOIN".to_string()]);
+}
+
+#[test]
+fn test_lookup_ignores_unrequested_prices() {
+    let requested = vec!["BNB".to_string()];
+    let prices = HashMap::from([price("BNB", 300.0), price("CAKE", 2.5)]);
+
+    let lookup = PriceLookup::new(&requested, prices);
+
+    assert!(!lookup.resolved.contains_key("CAKE"));
+    assert!(lookup.unresolved.is_empty());
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bnbmarket_price_service::{PriceProvider, PriceServiceImpl, ProviderError, TokenPrice};
+
+// In-memory provider with fixed prices that counts how often it is asked
+struct StaticProvider {
+    prices: HashMap<String, f64>,
+    calls: Arc<AtomicUsize>,
+}
+
+#[tonic::async_trait]
+impl PriceProvider for StaticProvider {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    async fn fetch(&self, tokens: &[String]) -> Result<HashMap<String, TokenPrice>, ProviderError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+
+        Ok(tokens.iter()
+            .filter_map(|token| {
+                let symbol = token.to_uppercase();
+                self.prices.get(&symbol).map(|&price| {
+                    (symbol.clone(), TokenPrice { symbol, price, source: self.name().to_string() })
+                })
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn test_custom_provider_is_consulted() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let provider = StaticProvider {
+        prices: HashMap::from([("BNB".to_string(), 312.5)]),
+        calls: calls.clone(),
+    };
+    let service = PriceServiceImpl::with_price_providers(vec![Box::new(provider)]);
+
+    let prices = service.fetch_prices(&["bnb".to_string(), "ETH".to_string()]).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(prices["BNB"].price, 312.5);
+    assert_eq!(prices["BNB"].source, "static");
+    assert!(!prices.contains_key("ETH"));
+    assert_eq!(service.supported_providers(), vec!["static".to_string()]);
+}
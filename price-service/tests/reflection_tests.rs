@@ -0,0 +1,54 @@
+use std::net::SocketAddr;
+
+use bnbmarket_price_service::reflection_service;
+use futures::stream;
+use tokio::net::TcpListener;
+use tonic::transport::{Channel, Server};
+use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::ServerReflectionRequest;
+
+async fn spawn_reflection_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(reflection_service().unwrap())
+            .serve_with_incoming(incoming)
+            .await
+            .unwrap();
+    });
+
+    addr
+}
+
+#[tokio::test]
+async fn test_reflection_lists_price_service() {
+    let addr = spawn_reflection_server().await;
+    let channel = Channel::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect()
+        .await
+        .expect("Reflection client should connect");
+    let mut client = ServerReflectionClient::new(channel);
+
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let mut responses = client.server_reflection_info(stream::iter(vec![request]))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = responses.message().await.unwrap().expect("Reflection should respond");
+    let services = match response.message_response {
+        Some(MessageResponse::ListServicesResponse(list)) => list.service,
+        other => panic!("Unexpected reflection response: {:?}", other),
+    };
+
+    assert!(services.iter().any(|service| service.name == "priceservice.PriceService"));
+}
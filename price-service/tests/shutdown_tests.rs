@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bnbmarket_price_service::{PriceProvider, PriceServiceImpl, ProviderError, TokenPrice};
+
+struct FixedProvider;
+
+#[tonic::async_trait]
+impl PriceProvider for FixedProvider {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    async fn fetch(&self, _tokens: &[String]) -> Result<HashMap<String, TokenPrice>, ProviderError> {
+        Ok(HashMap::from([("BNB".to_string(), TokenPrice {
+            symbol: "BNB".to_string(),
+            price: 300.0,
+            source: "fixed".to_string(),
+        })]))
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_aborts_subscriptions() {
+    let service = PriceServiceImpl::with_price_providers(vec![Box::new(FixedProvider)]);
+    let tokens = vec!["BNB".to_string()];
+
+    let mut first = service.spawn_subscription(tokens.clone(), Duration::from_millis(10)).unwrap();
+    let mut second = service.spawn_subscription(tokens.clone(), Duration::from_millis(10)).unwrap();
+    assert_eq!(first.recv().await.unwrap().price, 300.0);
+    assert_eq!(second.recv().await.unwrap().price, 300.0);
+    assert_eq!(service.active_subscriptions(), 2);
+
+    service.shutdown().await;
+    assert_eq!(service.active_subscriptions(), 0);
+
+    // Aborted tasks drop their senders, so the streams end after any buffered updates
+    for receiver in [&mut first, &mut second] {
+        let drained = tokio::time::timeout(Duration::from_secs(1), async {
+            while receiver.recv().await.is_some() {}
+        }).await;
+        assert!(drained.is_ok(), "Stream should close after shutdown");
+    }
+
+    assert!(service.spawn_subscription(tokens, Duration::from_millis(10)).is_err());
+}
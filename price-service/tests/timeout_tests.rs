@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use bnbmarket_price_service::{PriceServiceImpl, ProviderConfig};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// Minimal HTTP provider that answers every request after `delay`
+async fn spawn_mock_provider(name: &str, body: &'static str, delay: Duration) -> ProviderConfig {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(_) => return,
+            };
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 1024];
+                let _ = socket.read(&mut buffer).await;
+                tokio::time::sleep(delay).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    ProviderConfig {
+        name: name.to_string(),
+        url: format!("http://{}/prices", addr),
+        reliability: 1.0,
+    }
+}
+
+#[tokio::test]
+async fn test_slow_provider_is_omitted_after_timeout() {
+    let fast = spawn_mock_provider("fast", r#"{"bnb": 300.0}"#, Duration::ZERO).await;
+    let slow = spawn_mock_provider("slow", r#"{"bnb": 999.0}"#, Duration::from_secs(30)).await;
+
+    let service = PriceServiceImpl::with_providers(vec![fast, slow])
+        .with_request_timeout(Duration::from_millis(200))
+        .with_fetch_deadline(Duration::from_secs(1));
+
+    let started = Instant::now();
+    let prices = service.fetch_prices(&["BNB".to_string()]).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(2), "Slow provider should not block the fetch");
+    assert_eq!(prices["BNB"].price, 300.0);
+    assert_eq!(prices["BNB"].source, "fast");
+}
+
+#[tokio::test]
+async fn test_fetch_fails_when_every_provider_times_out() {
+    let slow = spawn_mock_provider("slow", r#"{"bnb": 300.0}"#, Duration::from_secs(30)).await;
+    let slower = spawn_mock_provider("slower", r#"{"bnb": 301.0}"#, Duration::from_secs(30)).await;
+
+    let service = PriceServiceImpl::with_providers(vec![slow, slower])
+        .with_request_timeout(Duration::from_millis(100))
+        .with_fetch_deadline(Duration::from_millis(500));
+
+    assert!(service.fetch_prices(&["BNB".to_string()]).await.is_err());
+}
@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+
+use bnbmarket_price_service::PriceServiceImpl;
+
+#[test]
+fn test_twap_matches_hand_computed_value() {
+    let service = PriceServiceImpl::with_price_providers(Vec::new());
+    let now = Instant::now();
+
+    // Outside the window; only sets the price at its start
+    service.record_price("BNB", now - Duration::from_secs(90), 50.0);
+    service.record_price("BNB", now - Duration::from_secs(40), 100.0);
+    service.record_price("BNB", now - Duration::from_secs(20), 110.0);
+    service.record_price("BNB", now - Duration::from_secs(10), 130.0);
+
+    // 60s window: 20s at 50, 20s at 100, 10s at 110, 10s at 130
+    let expected = (20.0 * 50.0 + 20.0 * 100.0 + 10.0 * 110.0 + 10.0 * 130.0) / 60.0;
+    let twap = service.get_twap("BNB", Duration::from_secs(60)).unwrap();
+
+    assert!((twap - expected).abs() < 0.01, "TWAP {} vs expected {}", twap, expected);
+}
+
+#[test]
+fn test_twap_needs_multiple_samples() {
+    let service = PriceServiceImpl::with_price_providers(Vec::new());
+
+    assert!(service.get_twap("BNB", Duration::from_secs(60)).is_none());
+
+    service.record_price("BNB", Instant::now() - Duration::from_secs(5), 300.0);
+    assert!(service.get_twap("BNB", Duration::from_secs(60)).is_none());
+}
@@ -1,15 +1,22 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use ethers::{
+    abi::{self, ParamType, Token},
     prelude::*,
     providers::{Http, Provider, RetryClient},
-    types::{Block, Transaction, TransactionReceipt}
+    types::{transaction::eip2718::TypedTransaction, Block, Transaction, TransactionReceipt}
 };
 use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Semaphore};
 use tokio::time;
 use thiserror::Error;
 
+#[cfg(feature = "oracle")]
+pub mod oracle;
+
 #[derive(Error, Debug)]
 pub enum RPCFetcherError {
     #[error("Provider connection error")]
@@ -20,12 +27,58 @@ pub enum RPCFetcherError {
 
     #[error("HTTP request error")]
     HttpError(#[from] reqwest::Error),
+
+    #[error("Oracle price is stale: last updated {0}s ago")]
+    StaleOracle(u64),
+
+    #[error("Price unavailable: {0}")]
+    PriceUnavailable(String),
+
+    #[error("Block not found: {0:?}")]
+    BlockNotFound(BlockNumber),
+
+    #[error("Transaction not found: {0:?}")]
+    TransactionNotFound(H256),
+
+    #[error("No liquidity for {0:?} against {1:?}")]
+    NoLiquidity(Address, Address),
+
+    #[error("RPC call timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("All RPC endpoints failed: {}", .0.join("; "))]
+    AllEndpointsFailed(Vec<String>),
+}
+
+impl RPCFetcherError {
+    /// Whether retrying the call, here or on another endpoint, could succeed.
+    /// Not-found and no-liquidity answers are definitive.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            RPCFetcherError::ProviderError(_)
+                | RPCFetcherError::HttpError(_)
+                | RPCFetcherError::Timeout(_)
+                | RPCFetcherError::AllEndpointsFailed(_)
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BNBChainRPCFetcher {
     provider: Provider<RetryClient<Http>>,
-    endpoints: Vec<&'static str>,
+    // Tried in order when the primary provider fails transiently
+    fallback_providers: Vec<Provider<RetryClient<Http>>>,
+    endpoints: Vec<String>,
+    // Upper bound on a single RPC call, retries included
+    call_timeout: Duration,
+    // Caps in-flight RPC calls; callers beyond the cap queue for a permit
+    in_flight: Arc<Semaphore>,
+    // Last metrics fetch, shared by callers within `metrics_ttl`
+    metrics_cache: Arc<Mutex<Option<(Instant, BlockchainMetrics)>>>,
+    metrics_ttl: Duration,
+    // Oracle answers older than this fall back to DEX spot
+    oracle_max_age: Duration,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,6 +89,25 @@ pub struct TokenPriceInfo {
     pub price_usd: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Chainlink,
+    PancakeSwap,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceQuote {
+    pub price: f64,
+    pub source: PriceSource,
+}
+
+// Latest answer from a Chainlink feed and when it was written (unix seconds)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OracleReading {
+    pub price: f64,
+    pub updated_at: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BlockchainMetrics {
     pub latest_block: u64,
@@ -54,34 +126,157 @@ impl BNBChainRPCFetcher {
         "https://bsc-dataseed4.defibit.io/",
     ];
 
+    // Default maximum number of concurrent RPC calls per fetcher
+    pub const DEFAULT_MAX_IN_FLIGHT: usize = 10;
+
+    // Default metrics cache lifetime, roughly one BNB Chain block
+    pub const DEFAULT_METRICS_TTL: Duration = Duration::from_secs(3);
+
+    // Default oracle staleness limit, matching Chainlink's hourly heartbeat
+    pub const DEFAULT_ORACLE_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+    // Default bound on a single RPC call: three retries of up to 10s each
+    pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
     pub fn new() -> Result<Self, RPCFetcherError> {
+        Self::with_endpoints(Self::DEFAULT_ENDPOINTS)
+    }
+
+    /// Fetcher over custom RPC endpoints; the first is the primary and the
+    /// rest are fallbacks, tried in order
+    pub fn with_endpoints(endpoints: &[&str]) -> Result<Self, RPCFetcherError> {
+        let (primary, fallbacks) = endpoints.split_first()
+            .ok_or_else(|| RPCFetcherError::AllEndpointsFailed(Vec::new()))?;
+        let provider = Self::client(primary)?;
+        let fallback_providers = fallbacks
+            .iter()
+            .map(|endpoint| Self::client(endpoint))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            provider,
+            fallback_providers,
+            endpoints: endpoints.iter().map(|endpoint| endpoint.to_string()).collect(),
+            call_timeout: Self::DEFAULT_CALL_TIMEOUT,
+            in_flight: Arc::new(Semaphore::new(Self::DEFAULT_MAX_IN_FLIGHT)),
+            metrics_cache: Arc::new(Mutex::new(None)),
+            metrics_ttl: Self::DEFAULT_METRICS_TTL,
+            oracle_max_age: Self::DEFAULT_ORACLE_MAX_AGE,
+        })
+    }
+
+    fn client(endpoint: &str) -> Result<Provider<RetryClient<Http>>, RPCFetcherError> {
         // Retry mechanism for RPC calls
         let provider = Provider::<RetryClient<Http>>::new_client(
-            Self::DEFAULT_ENDPOINTS[0],
+            endpoint,
             // Retry configuration
             RetryClientConfig::default()
                 .with_retries(3)
                 .with_timeout(Duration::from_secs(10))
         )?;
 
-        Ok(Self {
-            provider,
-            endpoints: Self::DEFAULT_ENDPOINTS,
-        })
+        Ok(provider)
+    }
+
+    /// Override the maximum number of concurrent RPC calls
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.in_flight = Arc::new(Semaphore::new(max_in_flight.max(1)));
+        self
+    }
+
+    /// Override how long fetched blockchain metrics are shared between callers
+    pub fn with_metrics_ttl(mut self, ttl: Duration) -> Self {
+        self.metrics_ttl = ttl;
+        self
+    }
+
+    /// Override how long a single RPC call may take before it times out
+    pub fn with_call_timeout(mut self, timeout: Duration) -> Self {
+        self.call_timeout = timeout;
+        self
+    }
+
+    /// Override how old an oracle answer may be before falling back to DEX spot
+    pub fn with_oracle_max_age(mut self, max_age: Duration) -> Self {
+        self.oracle_max_age = max_age;
+        self
+    }
+
+    /// Return cached metrics younger than the TTL, otherwise run `fetch`.
+    /// Concurrent callers wait on the same fetch rather than issuing their
+    /// own; failed fetches are not cached.
+    pub async fn coalesce_metrics<F, Fut>(&self, fetch: F) -> Result<BlockchainMetrics, RPCFetcherError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<BlockchainMetrics, RPCFetcherError>>,
+    {
+        let mut cache = self.metrics_cache.lock().await;
+        if let Some((fetched_at, metrics)) = cache.as_ref() {
+            if fetched_at.elapsed() < self.metrics_ttl {
+                return Ok(metrics.clone());
+            }
+        }
+
+        let metrics = fetch().await?;
+        *cache = Some((Instant::now(), metrics.clone()));
+        Ok(metrics)
+    }
+
+    /// Run an RPC call once a concurrency permit is available
+    pub async fn with_permit<F: Future>(&self, call: F) -> F::Output {
+        let _permit = self.in_flight.acquire().await
+            .expect("RPC semaphore is never closed");
+        call.await
+    }
+
+    /// Run an RPC call under the concurrency cap and the call timeout
+    pub async fn rpc_call<T, F>(&self, call: F) -> Result<T, RPCFetcherError>
+    where
+        F: Future<Output = Result<T, ProviderError>>,
+    {
+        time::timeout(self.call_timeout, self.with_permit(call)).await
+            .map_err(|_| RPCFetcherError::Timeout(self.call_timeout))?
+            .map_err(RPCFetcherError::from)
+    }
+
+    /// Run `call` against the primary provider, then each fallback in turn
+    /// while failures are transient. A definitive error such as a missing
+    /// transaction is returned as soon as any endpoint reports it.
+    pub async fn across_endpoints<'a, T, F, Fut>(&'a self, call: F) -> Result<T, RPCFetcherError>
+    where
+        F: Fn(&'a Provider<RetryClient<Http>>) -> Fut,
+        Fut: Future<Output = Result<T, RPCFetcherError>>,
+    {
+        let mut failures = Vec::new();
+        for provider in std::iter::once(&self.provider).chain(&self.fallback_providers) {
+            match call(provider).await {
+                Ok(value) => return Ok(value),
+                Err(e) if e.is_transient() => failures.push(e.to_string()),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(RPCFetcherError::AllEndpointsFailed(failures))
     }
 
     /// Fetch latest block information
     pub async fn get_latest_block(&self) -> Result<Block<Transaction>, RPCFetcherError> {
-        let block = self.provider.get_block_with_txs(BlockNumber::Latest).await?
-            .ok_or(RPCFetcherError::ProviderError(ProviderError::JsonRpcClientError))?;
-
-        Ok(block)
+        self.across_endpoints(|provider| async move {
+            self.rpc_call(provider.get_block_with_txs(BlockNumber::Latest)).await?
+                .ok_or(RPCFetcherError::BlockNotFound(BlockNumber::Latest))
+        }).await
     }
 
-    /// Fetch blockchain metrics
+    /// Fetch blockchain metrics, shared with other callers within the TTL
     pub async fn get_blockchain_metrics(&self) -> Result<BlockchainMetrics, RPCFetcherError> {
+        self.coalesce_metrics(|| self.fetch_blockchain_metrics()).await
+    }
+
+    async fn fetch_blockchain_metrics(&self) -> Result<BlockchainMetrics, RPCFetcherError> {
         let latest_block = self.get_latest_block().await?;
-        let gas_price = self.provider.get_gas_price().await?;
+        let gas_price = self.across_endpoints(|provider| async move {
+            self.rpc_call(provider.get_gas_price()).await
+        }).await?;
 
         Ok(BlockchainMetrics {
             latest_block: latest_block.number.unwrap_or_default().as_u64(),
@@ -90,7 +285,8 @@ impl BNBChainRPCFetcher {
         })
     }
 
-    /// Fetch token price from PancakeSwap Router
+    /// Fetch token price from PancakeSwap Router: the router's `getAmountsOut`
+    /// quote for one whole `token_address`, in units of `base_token`
     pub async fn fetch_token_price(
         &self,
         token_address: Address,
@@ -99,9 +295,168 @@ impl BNBChainRPCFetcher {
         // PancakeSwap V2 Router address
         let router_address: Address = "0x10ED43C718714eb63d5aA57B78B54704E256024E".parse().unwrap();
 
-        // Placeholder for actual price fetching logic
-        // In a real implementation, you'd call the router's `getAmountsOut` method
-        Ok(0.0)
+        self.across_endpoints(|provider| async move {
+            let token_decimals = self.decimals(provider, token_address).await?;
+            let base_decimals = self.decimals(provider, base_token).await?;
+            let amount_in = U256::from(10).checked_pow(U256::from(token_decimals))
+                .ok_or_else(|| Self::malformed("token decimals"))?;
+
+            // getAmountsOut(uint256,address[]) selector
+            let mut data = vec![0xd0, 0x6c, 0xa6, 0x1f];
+            data.extend(abi::encode(&[
+                Token::Uint(amount_in),
+                Token::Array(vec![Token::Address(token_address), Token::Address(base_token)]),
+            ]));
+            // The router reverts when the pair is missing or has no reserves
+            let amounts = self.call_contract(provider, router_address, data).await
+                .map_err(|e| if Self::is_revert(&e) {
+                    RPCFetcherError::NoLiquidity(token_address, base_token)
+                } else {
+                    e
+                })?;
+
+            let amounts = abi::decode(&[ParamType::Array(Box::new(ParamType::Uint(256)))], &amounts)
+                .map_err(|_| Self::malformed("PancakeSwap"))?;
+            let amount_out = match amounts.first() {
+                Some(Token::Array(amounts)) => match amounts.last() {
+                    Some(Token::Uint(amount_out)) => *amount_out,
+                    _ => return Err(Self::malformed("PancakeSwap")),
+                },
+                _ => return Err(Self::malformed("PancakeSwap")),
+            };
+            if amount_out.is_zero() {
+                return Err(RPCFetcherError::NoLiquidity(token_address, base_token));
+            }
+
+            Self::scale_down(amount_out, base_decimals)
+        }).await
+    }
+
+    /// Read the latest answer from a Chainlink aggregator feed
+    pub async fn fetch_chainlink_price(&self, feed: Address) -> Result<OracleReading, RPCFetcherError> {
+        self.across_endpoints(|provider| async move {
+            // A wrong or retired feed reverts; that is final, so the caller can
+            // move on to its fallback instead of trying every endpoint
+            let reverted = |e: RPCFetcherError| if Self::is_revert(&e) {
+                RPCFetcherError::PriceUnavailable(format!("Chainlink feed {:?} reverted", feed))
+            } else {
+                e
+            };
+
+            // latestRoundData() selector
+            let round = self.call_contract(provider, feed, vec![0xfe, 0xaf, 0x96, 0x8c]).await.map_err(reverted)?;
+            let decimals = self.decimals(provider, feed).await.map_err(reverted)?;
+
+            let round = abi::decode(
+                &[ParamType::Uint(80), ParamType::Int(256), ParamType::Uint(256), ParamType::Uint(256), ParamType::Uint(80)],
+                &round,
+            ).map_err(|_| Self::malformed("Chainlink"))?;
+
+            let (answer, updated_at) = match (&round[1], &round[3]) {
+                (Token::Int(answer), Token::Uint(updated_at)) => (*answer, *updated_at),
+                _ => return Err(Self::malformed("Chainlink")),
+            };
+            let answer = I256::from_raw(answer);
+            if answer <= I256::zero() {
+                return Err(RPCFetcherError::PriceUnavailable(format!("Non-positive oracle answer {}", answer)));
+            }
+            let updated_at = u64::try_from(updated_at).map_err(|_| {
+                RPCFetcherError::PriceUnavailable(format!("Oracle timestamp {} out of range", updated_at))
+            })?;
+
+            Ok(OracleReading {
+                price: Self::scale_down(answer.into_raw(), decimals)?,
+                updated_at,
+            })
+        }).await
+    }
+
+    // eth_call against one endpoint, under the concurrency cap and timeout
+    async fn call_contract(
+        &self,
+        provider: &Provider<RetryClient<Http>>,
+        to: Address,
+        data: Vec<u8>
+    ) -> Result<Bytes, RPCFetcherError> {
+        let call: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+        self.rpc_call(provider.call(&call, None)).await
+    }
+
+    // `decimals()` of an ERC-20 token or Chainlink feed
+    async fn decimals(&self, provider: &Provider<RetryClient<Http>>, contract: Address) -> Result<u32, RPCFetcherError> {
+        let decimals = self.call_contract(provider, contract, vec![0x31, 0x3c, 0xe5, 0x67]).await?;
+        match abi::decode(&[ParamType::Uint(8)], &decimals).map_err(|_| Self::malformed("decimals"))?.as_slice() {
+            [Token::Uint(decimals)] => u32::try_from(*decimals).map_err(|_| Self::malformed("decimals")),
+            _ => Err(Self::malformed("decimals")),
+        }
+    }
+
+    // On-chain integer `amount` with `decimals` places as a float
+    fn scale_down(amount: U256, decimals: u32) -> Result<f64, RPCFetcherError> {
+        ethers::utils::format_units(amount, decimals).ok()
+            .and_then(|formatted| formatted.parse::<f64>().ok())
+            .ok_or_else(|| RPCFetcherError::PriceUnavailable(format!("Cannot scale {} by {} decimals", amount, decimals)))
+    }
+
+    // An eth_call the contract itself rejected; every endpoint would answer
+    // the same, so it is not worth retrying
+    fn is_revert(error: &RPCFetcherError) -> bool {
+        matches!(error, RPCFetcherError::ProviderError(e) if e.to_string().contains("execution reverted"))
+    }
+
+    fn malformed(source: &str) -> RPCFetcherError {
+        RPCFetcherError::PriceUnavailable(format!("Malformed {} response", source))
+    }
+
+    /// Price `token` in `base`, preferring the Chainlink feed and falling back
+    /// to PancakeSwap spot when the oracle is stale or unreachable
+    pub async fn get_price(
+        &self,
+        token: Address,
+        base: Address,
+        chainlink_feed: Address
+    ) -> Result<PriceQuote, RPCFetcherError> {
+        self.price_with_fallback(
+            self.fetch_chainlink_price(chainlink_feed),
+            self.fetch_token_price(token, base),
+        ).await
+    }
+
+    /// Resolve a price from an oracle source, falling back to a DEX source.
+    /// The DEX is only queried if the oracle fails or is older than the
+    /// configured max age; non-positive prices count as failures.
+    pub async fn price_with_fallback<O, D>(&self, oracle: O, dex: D) -> Result<PriceQuote, RPCFetcherError>
+    where
+        O: Future<Output = Result<OracleReading, RPCFetcherError>>,
+        D: Future<Output = Result<f64, RPCFetcherError>>,
+    {
+        let oracle_failure = match oracle.await {
+            Ok(reading) if reading.price.is_finite() && reading.price > 0.0 => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let age = now.saturating_sub(reading.updated_at);
+                if age <= self.oracle_max_age.as_secs() {
+                    return Ok(PriceQuote { price: reading.price, source: PriceSource::Chainlink });
+                }
+                RPCFetcherError::StaleOracle(age)
+            }
+            Ok(reading) => RPCFetcherError::PriceUnavailable(format!("Invalid oracle price {}", reading.price)),
+            Err(e) => e,
+        };
+
+        match dex.await {
+            Ok(price) if price.is_finite() && price > 0.0 => {
+                Ok(PriceQuote { price, source: PriceSource::PancakeSwap })
+            }
+            Ok(price) => Err(RPCFetcherError::PriceUnavailable(
+                format!("oracle failed ({}) and DEX returned invalid price {}", oracle_failure, price)
+            )),
+            Err(e) => Err(RPCFetcherError::PriceUnavailable(
+                format!("oracle failed ({}) and DEX failed ({})", oracle_failure, e)
+            )),
+        }
     }
 
     /// Fetch transaction details
@@ -109,12 +464,14 @@ impl BNBChainRPCFetcher {
         &self,
         tx_hash: H256
     ) -> Result<(Transaction, Option<TransactionReceipt>), RPCFetcherError> {
-        let transaction = self.provider.get_transaction(tx_hash).await?
-            .ok_or(RPCFetcherError::ProviderError(ProviderError::JsonRpcClientError))?;
+        self.across_endpoints(|provider| async move {
+            let transaction = self.rpc_call(provider.get_transaction(tx_hash)).await?
+                .ok_or(RPCFetcherError::TransactionNotFound(tx_hash))?;
 
-        let receipt = self.provider.get_transaction_receipt(tx_hash).await?;
+            let receipt = self.rpc_call(provider.get_transaction_receipt(tx_hash)).await?;
 
-        Ok((transaction, receipt))
+            Ok((transaction, receipt))
+        }).await
     }
 
     /// Periodic metrics update stream
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use ethers::types::Address;
+use rust_lmsr::{OracleError, OutcomeOracle, SettlementOutcome};
+
+use crate::BNBChainRPCFetcher;
+
+// Binary market on whether a feed's price is at or above `strike` once
+// `resolves_at` (unix seconds) has passed
+#[derive(Debug, Clone, Copy)]
+pub struct PriceThreshold {
+    pub feed: Address,
+    pub strike: f64,
+    pub resolves_at: u64,
+}
+
+// Settles price-threshold markets from Chainlink feeds: outcome 0 wins if
+// the feed answers at or above the strike, outcome 1 otherwise
+#[derive(Debug, Clone)]
+pub struct ChainlinkOracle {
+    fetcher: BNBChainRPCFetcher,
+    markets: HashMap<String, PriceThreshold>,
+}
+
+impl ChainlinkOracle {
+    pub fn new(fetcher: BNBChainRPCFetcher) -> Self {
+        ChainlinkOracle { fetcher, markets: HashMap::new() }
+    }
+
+    pub fn add_market(&mut self, market_id: &str, threshold: PriceThreshold) {
+        self.markets.insert(market_id.to_string(), threshold);
+    }
+}
+
+impl OutcomeOracle for ChainlinkOracle {
+    // Blocks on the feed read: call it from a multi-threaded tokio runtime
+    // or from outside any runtime
+    fn resolve(&self, market_id: &str) -> Result<SettlementOutcome, OracleError> {
+        let market = self.markets.get(market_id)
+            .ok_or_else(|| OracleError::Unavailable(format!("No feed configured for market {}", market_id)))?;
+
+        let reading = block_on(self.fetcher.fetch_chainlink_price(market.feed))?
+            .map_err(|e| OracleError::Unavailable(e.to_string()))?;
+        // An answer written before the resolution time doesn't settle anything yet
+        if reading.updated_at < market.resolves_at {
+            return Err(OracleError::Unresolved(market_id.to_string()));
+        }
+
+        Ok(SettlementOutcome::Winner(if reading.price >= market.strike { 0 } else { 1 }))
+    }
+}
+
+// Runs `future` from the synchronous oracle interface, on the current
+// runtime if there is one, otherwise on a fresh single-threaded one
+fn block_on<F: Future>(future: F) -> Result<F::Output, OracleError> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => Ok(tokio::task::block_in_place(|| handle.block_on(future))),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map(|runtime| runtime.block_on(future))
+            .map_err(|e| OracleError::Unavailable(e.to_string())),
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "oracle")]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bnb_rpc_fetcher::oracle::{ChainlinkOracle, PriceThreshold};
+use bnb_rpc_fetcher::BNBChainRPCFetcher;
+use ethers::abi::{self, Token};
+use ethers::prelude::{Address, Bytes, U256};
+use rust_lmsr::{
+    Bet, MarketConfig, MarketError, MarketType, OracleError, OutcomeOracle, SettlementEngine, SettlementOutcome,
+};
+use serde_json::json;
+
+mod common;
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn feed() -> Address {
+    Address::repeat_byte(0x03)
+}
+
+// 8-decimal feed answering 312.5, last written at `updated_at`
+async fn feed_oracle(updated_at: u64, strike: f64, resolves_at: u64) -> ChainlinkOracle {
+    let endpoint = common::spawn_mock_rpc(move |_, params| {
+        let data = params[0]["data"].as_str().or_else(|| params[0]["input"].as_str()).unwrap();
+        let output = match &data[2..10] {
+            "feaf968c" => abi::encode(&[
+                Token::Uint(1.into()),
+                Token::Int(U256::from(31_250_000_000u64)),
+                Token::Uint(updated_at.into()),
+                Token::Uint(updated_at.into()),
+                Token::Uint(1.into()),
+            ]),
+            "313ce567" => abi::encode(&[Token::Uint(8.into())]),
+            selector => return Err(format!("Unexpected selector {}", selector)),
+        };
+        Ok(json!(Bytes::from(output)))
+    }).await;
+
+    let mut oracle = ChainlinkOracle::new(BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap());
+    oracle.add_market("bnb-above-300", PriceThreshold { feed: feed(), strike, resolves_at });
+    oracle
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_feed_drives_market_to_settlement() {
+    let oracle = feed_oracle(now() - 30, 300.0, now() - 60).await;
+    let engine = SettlementEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary)).with_oracle(Box::new(oracle));
+    let bets = vec![Bet { option_id: 0, amount: 10.0 }, Bet { option_id: 1, amount: 5.0 }];
+
+    let ledger = engine.auto_resolve("bnb-above-300", &bets).unwrap();
+
+    assert_eq!(ledger.outcome, SettlementOutcome::Winner(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_answer_below_strike_picks_the_other_outcome() {
+    let oracle = feed_oracle(now() - 30, 400.0, now() - 60).await;
+
+    assert_eq!(oracle.resolve("bnb-above-300").unwrap(), SettlementOutcome::Winner(1));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_answer_before_resolution_time_leaves_market_open() {
+    let oracle = feed_oracle(now() - 30, 300.0, now() + 3600).await;
+    let engine = SettlementEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary)).with_oracle(Box::new(oracle));
+
+    assert!(matches!(
+        engine.auto_resolve("bnb-above-300", &[]),
+        Err(MarketError::Oracle(OracleError::Unresolved(_)))
+    ));
+    assert!(matches!(
+        engine.auto_resolve("unknown-market", &[]),
+        Err(MarketError::Oracle(OracleError::Unavailable(_)))
+    ));
+}
@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bnb_rpc_fetcher::BNBChainRPCFetcher;
+
+#[tokio::test]
+async fn test_in_flight_calls_never_exceed_cap() {
+    let fetcher = Arc::new(BNBChainRPCFetcher::new().unwrap().with_max_in_flight(3));
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let calls: Vec<_> = (0..20).map(|_| {
+        let fetcher = fetcher.clone();
+        let in_flight = in_flight.clone();
+        let peak = peak.clone();
+
+        tokio::spawn(async move {
+            // Mock RPC call that records how many calls overlap
+            fetcher.with_permit(async {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            }).await
+        })
+    }).collect();
+
+    for call in calls {
+        call.await.expect("Queued calls should complete, not fail");
+    }
+
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+}
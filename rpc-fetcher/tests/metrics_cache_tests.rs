@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bnb_rpc_fetcher::{BNBChainRPCFetcher, BlockchainMetrics};
+use ethers::types::U256;
+
+// Mock RPC round-trip that counts how often it actually runs
+async fn mock_fetch(fetches: Arc<AtomicUsize>) -> Result<BlockchainMetrics, bnb_rpc_fetcher::RPCFetcherError> {
+    let block = fetches.fetch_add(1, Ordering::SeqCst) as u64 + 1;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    Ok(BlockchainMetrics {
+        latest_block: block,
+        network_hashrate: 0,
+        gas_price: U256::from(5_000_000_000u64),
+    })
+}
+
+#[tokio::test]
+async fn test_concurrent_metric_calls_share_one_fetch() {
+    let fetcher = Arc::new(BNBChainRPCFetcher::new().unwrap().with_metrics_ttl(Duration::from_millis(200)));
+    let fetches = Arc::new(AtomicUsize::new(0));
+
+    let calls: Vec<_> = (0..10).map(|_| {
+        let fetcher = fetcher.clone();
+        let fetches = fetches.clone();
+        tokio::spawn(async move {
+            fetcher.coalesce_metrics(|| mock_fetch(fetches)).await.unwrap()
+        })
+    }).collect();
+
+    for call in calls {
+        assert_eq!(call.await.unwrap().latest_block, 1);
+    }
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+    // Past the TTL the next call goes back to the chain
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let refreshed = fetcher.coalesce_metrics(|| mock_fetch(fetches.clone())).await.unwrap();
+    assert_eq!(refreshed.latest_block, 2);
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,189 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bnb_rpc_fetcher::{BNBChainRPCFetcher, OracleReading, PriceSource, RPCFetcherError};
+use ethers::abi::{self, Token};
+use ethers::prelude::{Address, Bytes, U256};
+use serde_json::{json, Value};
+
+mod common;
+
+fn seconds_ago(seconds: u64) -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() - seconds
+}
+
+fn fetcher() -> BNBChainRPCFetcher {
+    BNBChainRPCFetcher::new().unwrap().with_oracle_max_age(Duration::from_secs(600))
+}
+
+fn token() -> Address {
+    Address::repeat_byte(0x01)
+}
+
+fn base() -> Address {
+    Address::repeat_byte(0x02)
+}
+
+fn feed() -> Address {
+    Address::repeat_byte(0x03)
+}
+
+// Answers eth_calls like a chain with an 8-decimal BNB/USD feed reading
+// 312.5 and a PancakeSwap pool quoting `amount_out` of the 18-decimal base
+// for one whole 18-decimal token
+fn mock_chain(updated_at: U256, amount_out: U256) -> impl Fn(&str, &Value) -> Result<Value, String> {
+    move |method, params| {
+        assert_eq!(method, "eth_call");
+        let call = &params[0];
+        let to: Address = call["to"].as_str().unwrap().parse().unwrap();
+        let data = call["data"].as_str().or_else(|| call["input"].as_str()).unwrap();
+
+        let output = match &data[2..10] {
+            "feaf968c" => abi::encode(&[
+                Token::Uint(1.into()),
+                Token::Int(U256::from(31_250_000_000u64)),
+                Token::Uint(updated_at),
+                Token::Uint(updated_at),
+                Token::Uint(1.into()),
+            ]),
+            "313ce567" if to == feed() => abi::encode(&[Token::Uint(8.into())]),
+            "313ce567" => abi::encode(&[Token::Uint(18.into())]),
+            "d06ca61f" => abi::encode(&[Token::Array(vec![Token::Uint(U256::exp10(18)), Token::Uint(amount_out)])]),
+            selector => return Err(format!("Unexpected selector {}", selector)),
+        };
+        Ok(json!(Bytes::from(output)))
+    }
+}
+
+#[tokio::test]
+async fn test_fresh_oracle_price_is_used() {
+    let dex_queried = AtomicBool::new(false);
+
+    let quote = fetcher().price_with_fallback(
+        async { Ok(OracleReading { price: 312.5, updated_at: seconds_ago(30) }) },
+        async {
+            dex_queried.store(true, Ordering::SeqCst);
+            Ok(310.0)
+        },
+    ).await.unwrap();
+
+    assert_eq!(quote.source, PriceSource::Chainlink);
+    assert_eq!(quote.price, 312.5);
+    assert!(!dex_queried.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_stale_oracle_falls_back_to_dex() {
+    let quote = fetcher().price_with_fallback(
+        async { Ok(OracleReading { price: 312.5, updated_at: seconds_ago(3600) }) },
+        async { Ok(310.0) },
+    ).await.unwrap();
+
+    assert_eq!(quote.source, PriceSource::PancakeSwap);
+    assert_eq!(quote.price, 310.0);
+}
+
+#[tokio::test]
+async fn test_both_sources_failing_is_an_error() {
+    let result = fetcher().price_with_fallback(
+        async { Err(RPCFetcherError::PriceUnavailable("feed unreachable".to_string())) },
+        async { Ok(0.0) },
+    ).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::PriceUnavailable(_))));
+}
+
+#[tokio::test]
+async fn test_stale_feed_falls_back_to_pancakeswap_quote() {
+    let endpoint = common::spawn_mock_rpc(mock_chain(seconds_ago(3600).into(), U256::exp10(18) * 310)).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap()
+        .with_oracle_max_age(Duration::from_secs(600));
+
+    let quote = fetcher.get_price(token(), base(), feed()).await.unwrap();
+
+    assert_eq!(quote.source, PriceSource::PancakeSwap);
+    assert_eq!(quote.price, 310.0);
+}
+
+#[tokio::test]
+async fn test_fresh_feed_is_read_from_chain() {
+    let endpoint = common::spawn_mock_rpc(mock_chain(seconds_ago(30).into(), U256::exp10(18) * 310)).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap()
+        .with_oracle_max_age(Duration::from_secs(600));
+
+    let quote = fetcher.get_price(token(), base(), feed()).await.unwrap();
+
+    assert_eq!(quote.source, PriceSource::Chainlink);
+    assert_eq!(quote.price, 312.5);
+}
+
+#[tokio::test]
+async fn test_feed_read_falls_back_to_next_endpoint() {
+    let failing = common::spawn_mock_rpc(|_, _| Err("unavailable".to_string())).await;
+    let healthy = common::spawn_mock_rpc(mock_chain(seconds_ago(30).into(), U256::exp10(18))).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&failing, &healthy]).unwrap();
+
+    let reading = fetcher.fetch_chainlink_price(feed()).await.unwrap();
+
+    assert_eq!(reading.price, 312.5);
+}
+
+#[tokio::test]
+async fn test_out_of_range_feed_timestamp_is_unavailable() {
+    let endpoint = common::spawn_mock_rpc(mock_chain(U256::MAX, U256::exp10(18))).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap();
+
+    let result = fetcher.fetch_chainlink_price(feed()).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::PriceUnavailable(_))));
+}
+
+#[tokio::test]
+async fn test_empty_pool_maps_to_no_liquidity() {
+    let endpoint = common::spawn_mock_rpc(mock_chain(seconds_ago(30).into(), U256::zero())).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap();
+
+    let result = fetcher.fetch_token_price(token(), base()).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::NoLiquidity(t, b)) if t == token() && b == base()));
+}
+
+#[tokio::test]
+async fn test_reverting_router_maps_to_no_liquidity() {
+    let chain = mock_chain(seconds_ago(30).into(), U256::exp10(18));
+    let endpoint = common::spawn_mock_rpc(move |method, params| {
+        let data = params[0]["data"].as_str().or_else(|| params[0]["input"].as_str()).unwrap_or_default();
+        if data.starts_with("0xd06ca61f") {
+            return Err("execution reverted: PancakeLibrary: INSUFFICIENT_LIQUIDITY".to_string());
+        }
+        chain(method, params)
+    }).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint, &endpoint]).unwrap();
+
+    let result = fetcher.fetch_token_price(token(), base()).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::NoLiquidity(t, b)) if t == token() && b == base()));
+}
+
+#[tokio::test]
+async fn test_reverting_feed_falls_back_to_dex_without_retrying() {
+    let chain = mock_chain(seconds_ago(30).into(), U256::exp10(18) * 310);
+    let feed_calls = Arc::new(AtomicUsize::new(0));
+    let counted = feed_calls.clone();
+    let endpoint = common::spawn_mock_rpc(move |method, params| {
+        let to: Address = params[0]["to"].as_str().unwrap().parse().unwrap();
+        if to == feed() {
+            counted.fetch_add(1, Ordering::SeqCst);
+            return Err("execution reverted".to_string());
+        }
+        chain(method, params)
+    }).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint, &endpoint, &endpoint]).unwrap();
+
+    let quote = fetcher.get_price(token(), base(), feed()).await.unwrap();
+
+    assert_eq!(quote.source, PriceSource::PancakeSwap);
+    assert_eq!(quote.price, 310.0);
+    assert_eq!(feed_calls.load(Ordering::SeqCst), 1, "A revert should not be retried on other endpoints");
+}
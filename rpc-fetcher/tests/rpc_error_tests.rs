@@ -0,0 +1,120 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use bnb_rpc_fetcher::{BNBChainRPCFetcher, RPCFetcherError};
+use ethers::prelude::{BlockNumber, ProviderError, H256};
+use serde_json::{json, Value};
+
+mod common;
+
+#[tokio::test]
+async fn test_slow_call_maps_to_timeout() {
+    let fetcher = BNBChainRPCFetcher::new().unwrap().with_call_timeout(Duration::from_millis(20));
+
+    let result = fetcher.rpc_call(futures::future::pending::<Result<u64, ProviderError>>()).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::Timeout(timeout)) if timeout == Duration::from_millis(20)));
+    assert!(result.unwrap_err().is_transient());
+}
+
+#[tokio::test]
+async fn test_transient_failures_on_every_endpoint_are_collected() {
+    let fetcher = BNBChainRPCFetcher::new().unwrap();
+    let attempts = AtomicUsize::new(0);
+
+    let result: Result<u64, _> = fetcher.across_endpoints(|_| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(RPCFetcherError::Timeout(Duration::from_secs(1)))
+    }).await;
+
+    let tried = attempts.load(Ordering::SeqCst);
+    assert!(tried > 1, "Fallback endpoints should be tried");
+    assert!(matches!(result, Err(RPCFetcherError::AllEndpointsFailed(failures)) if failures.len() == tried));
+}
+
+#[tokio::test]
+async fn test_not_found_stops_endpoint_fallback() {
+    let fetcher = BNBChainRPCFetcher::new().unwrap();
+    let attempts = AtomicUsize::new(0);
+    let tx_hash = H256::repeat_byte(0xab);
+
+    let result: Result<u64, _> = fetcher.across_endpoints(|_| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(RPCFetcherError::TransactionNotFound(tx_hash))
+    }).await;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    assert!(matches!(result, Err(RPCFetcherError::TransactionNotFound(hash)) if hash == tx_hash));
+    assert!(!result.unwrap_err().is_transient());
+}
+
+#[tokio::test]
+async fn test_missing_block_maps_to_block_not_found() {
+    let endpoint = common::spawn_mock_rpc(|_, _| Ok(Value::Null)).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap();
+
+    let result = fetcher.get_latest_block().await;
+
+    assert!(matches!(result, Err(RPCFetcherError::BlockNotFound(BlockNumber::Latest))));
+}
+
+#[tokio::test]
+async fn test_missing_transaction_maps_to_transaction_not_found() {
+    let endpoint = common::spawn_mock_rpc(|method, _| {
+        assert_eq!(method, "eth_getTransactionByHash");
+        Ok(Value::Null)
+    }).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint]).unwrap();
+    let tx_hash = H256::repeat_byte(0xab);
+
+    let result = fetcher.get_transaction_details(tx_hash).await;
+
+    assert!(matches!(result, Err(RPCFetcherError::TransactionNotFound(hash)) if hash == tx_hash));
+}
+
+#[tokio::test]
+async fn test_gas_price_fails_over_with_the_block() {
+    let failing = common::spawn_mock_rpc(|_, _| Err("unavailable".to_string())).await;
+    let healthy = common::spawn_mock_rpc(|method, _| match method {
+        "eth_getBlockByNumber" => Ok(json!({
+            "number": "0x2a",
+            "hash": format!("0x{}", "11".repeat(32)),
+            "parentHash": format!("0x{}", "22".repeat(32)),
+            "sha3Uncles": format!("0x{}", "33".repeat(32)),
+            "miner": format!("0x{}", "44".repeat(20)),
+            "stateRoot": format!("0x{}", "55".repeat(32)),
+            "transactionsRoot": format!("0x{}", "66".repeat(32)),
+            "receiptsRoot": format!("0x{}", "77".repeat(32)),
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x2",
+            "totalDifficulty": "0x2",
+            "gasUsed": "0x0",
+            "gasLimit": "0x1c9c380",
+            "extraData": "0x",
+            "timestamp": "0x6500",
+            "size": "0x200",
+            "mixHash": format!("0x{}", "00".repeat(32)),
+            "nonce": "0x0000000000000000",
+            "uncles": [],
+            "transactions": [],
+        })),
+        "eth_gasPrice" => Ok(json!("0x12a05f200")),
+        other => Err(format!("Unexpected method {}", other)),
+    }).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&failing, &healthy]).unwrap();
+
+    let metrics = fetcher.get_blockchain_metrics().await.unwrap();
+
+    assert_eq!(metrics.latest_block, 42);
+    assert_eq!(metrics.gas_price, 5_000_000_000u64.into());
+}
+
+#[tokio::test]
+async fn test_gas_price_failing_everywhere_maps_to_all_endpoints_failed() {
+    let endpoint = common::spawn_mock_rpc(|_, _| Err("unavailable".to_string())).await;
+    let fetcher = BNBChainRPCFetcher::with_endpoints(&[&endpoint, &endpoint]).unwrap();
+
+    let result = fetcher.get_blockchain_metrics().await;
+
+    assert!(matches!(result, Err(RPCFetcherError::AllEndpointsFailed(failures)) if failures.len() == 2));
+}
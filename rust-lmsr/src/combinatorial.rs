@@ -0,0 +1,32 @@
+use crate::MarketError;
+
+// Fair price of a conjunction across independent markets: the product of
+// each market's selected outcome probability. `outcome_selection[i]` picks
+// the outcome in `markets[i]`.
+pub fn joint_probability(markets: &[&[f64]], outcome_selection: &[usize]) -> Result<f64, MarketError> {
+    if markets.is_empty() {
+        return Err(MarketError::InsufficientData("Joint probability needs at least one market".to_string()));
+    }
+    if markets.len() != outcome_selection.len() {
+        return Err(MarketError::CalculationError(format!(
+            "Got {} markets but {} outcome selections",
+            markets.len(),
+            outcome_selection.len()
+        )));
+    }
+
+    markets.iter()
+        .zip(outcome_selection)
+        .try_fold(1.0, |joint, (probabilities, &outcome)| {
+            let probability = probabilities.get(outcome)
+                .copied()
+                .ok_or(MarketError::InvalidOutcomeIndex(outcome))?;
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(MarketError::CalculationError(
+                    format!("Invalid probability {} for outcome {}", probability, outcome)
+                ));
+            }
+
+            Ok(joint * probability)
+        })
+}
@@ -0,0 +1,73 @@
+use crate::MarketError;
+
+// How far a distribution's sum may stray from 1
+const SUM_TOLERANCE: f64 = 1e-6;
+
+// KL(p || q) = Σ p_i·ln(p_i / q_i), in nats. Infinite when q assigns zero
+// probability to an outcome p considers possible, which is reported as an error.
+pub fn kl_divergence(p: &[f64], q: &[f64]) -> Result<f64, MarketError> {
+    validate_pair(p, q)?;
+
+    p.iter()
+        .zip(q)
+        .enumerate()
+        .filter(|(_, (&p_i, _))| p_i > 0.0)
+        .map(|(index, (&p_i, &q_i))| {
+            if q_i <= 0.0 {
+                return Err(unbounded(index));
+            }
+            Ok(p_i * (p_i / q_i).ln())
+        })
+        .sum()
+}
+
+// H(p, q) = -Σ p_i·ln(q_i), in nats. Equals H(p) + KL(p || q).
+pub fn cross_entropy(p: &[f64], q: &[f64]) -> Result<f64, MarketError> {
+    validate_pair(p, q)?;
+
+    p.iter()
+        .zip(q)
+        .enumerate()
+        .filter(|(_, (&p_i, _))| p_i > 0.0)
+        .map(|(index, (&p_i, &q_i))| {
+            if q_i <= 0.0 {
+                return Err(unbounded(index));
+            }
+            Ok(-p_i * q_i.ln())
+        })
+        .sum()
+}
+
+fn unbounded(index: usize) -> MarketError {
+    MarketError::CalculationError(
+        format!("Outcome {} has zero reference probability; divergence is unbounded", index)
+    )
+}
+
+fn validate_pair(p: &[f64], q: &[f64]) -> Result<(), MarketError> {
+    if p.len() != q.len() {
+        return Err(MarketError::CalculationError(
+            format!("Distributions differ in length ({} vs {})", p.len(), q.len())
+        ));
+    }
+    validate_distribution(p)?;
+    validate_distribution(q)
+}
+
+fn validate_distribution(distribution: &[f64]) -> Result<(), MarketError> {
+    if distribution.is_empty() {
+        return Err(MarketError::InsufficientData("Distribution has no outcomes".to_string()));
+    }
+    if let Some(&value) = distribution.iter().find(|v| !v.is_finite() || **v < 0.0) {
+        return Err(MarketError::CalculationError(format!("Invalid probability {}", value)));
+    }
+
+    let total: f64 = distribution.iter().sum();
+    if (total - 1.0).abs() > SUM_TOLERANCE {
+        return Err(MarketError::CalculationError(
+            format!("Distribution sums to {}, expected 1", total)
+        ));
+    }
+
+    Ok(())
+}
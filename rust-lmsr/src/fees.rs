@@ -0,0 +1,215 @@
+use crate::MarketError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Fee rate that applies once a user's cumulative volume reaches `min_volume`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FeeTier {
+    pub min_volume: f64,
+    pub fee_bps: u32,
+}
+
+// Volume-tiered fees. Tiers start at zero volume, thresholds strictly
+// increase and fees never rise with volume; this is checked on construction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "Vec<FeeTier>", into = "Vec<FeeTier>")]
+pub struct FeeSchedule {
+    tiers: Vec<FeeTier>,
+}
+
+impl FeeSchedule {
+    pub fn new(tiers: Vec<FeeTier>) -> Result<Self, MarketError> {
+        let first = tiers.first()
+            .ok_or_else(|| MarketError::InvalidFeeSchedule("Schedule needs at least one tier".to_string()))?;
+        if first.min_volume != 0.0 {
+            return Err(MarketError::InvalidFeeSchedule(
+                format!("First tier must start at volume 0, got {}", first.min_volume)
+            ));
+        }
+
+        for pair in tiers.windows(2) {
+            let (lower, upper) = (pair[0], pair[1]);
+            if !upper.min_volume.is_finite() || upper.min_volume <= lower.min_volume {
+                return Err(MarketError::InvalidFeeSchedule(
+                    format!("Tier thresholds must strictly increase ({} then {})", lower.min_volume, upper.min_volume)
+                ));
+            }
+            if upper.fee_bps > lower.fee_bps {
+                return Err(MarketError::InvalidFeeSchedule(
+                    format!("Fee rises from {} to {} bps at volume {}", lower.fee_bps, upper.fee_bps, upper.min_volume)
+                ));
+            }
+        }
+
+        Ok(FeeSchedule { tiers })
+    }
+
+    // Single-tier schedule equivalent to a flat fee
+    pub fn flat(fee_bps: u32) -> Self {
+        FeeSchedule { tiers: vec![FeeTier { min_volume: 0.0, fee_bps }] }
+    }
+
+    pub fn tiers(&self) -> &[FeeTier] {
+        &self.tiers
+    }
+
+    // Rate for a user with `user_volume` of cumulative volume
+    pub fn fee_for(&self, user_volume: f64) -> u32 {
+        self.tiers.iter()
+            .take_while(|tier| tier.min_volume <= user_volume)
+            .last()
+            .unwrap_or(&self.tiers[0])
+            .fee_bps
+    }
+
+    // Fee charged on a trade of `amount` by a user with `user_volume`
+    pub fn fee_on(&self, amount: f64, user_volume: f64) -> f64 {
+        amount * self.fee_for(user_volume) as f64 / 10_000.0
+    }
+}
+
+impl TryFrom<Vec<FeeTier>> for FeeSchedule {
+    type Error = MarketError;
+
+    fn try_from(tiers: Vec<FeeTier>) -> Result<Self, Self::Error> {
+        FeeSchedule::new(tiers)
+    }
+}
+
+impl From<FeeSchedule> for Vec<FeeTier> {
+    fn from(schedule: FeeSchedule) -> Self {
+        schedule.tiers
+    }
+}
+
+// How collected fees are divided between the treasury and burning.
+// Shares are in bps and must add up to 10_000.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "FeeDistributionFields")]
+pub struct FeeDistribution {
+    pub treasury_bps: u32,
+    pub burn_bps: u32,
+}
+
+// Unchecked wire form of `FeeDistribution`
+#[derive(Deserialize)]
+struct FeeDistributionFields {
+    treasury_bps: u32,
+    burn_bps: u32,
+}
+
+impl TryFrom<FeeDistributionFields> for FeeDistribution {
+    type Error = MarketError;
+
+    fn try_from(fields: FeeDistributionFields) -> Result<Self, Self::Error> {
+        FeeDistribution::new(fields.treasury_bps, fields.burn_bps)
+    }
+}
+
+impl FeeDistribution {
+    pub fn new(treasury_bps: u32, burn_bps: u32) -> Result<Self, MarketError> {
+        let distribution = FeeDistribution { treasury_bps, burn_bps };
+        distribution.validate()?;
+        Ok(distribution)
+    }
+
+    pub fn validate(&self) -> Result<(), MarketError> {
+        if self.treasury_bps.checked_add(self.burn_bps) != Some(10_000) {
+            return Err(MarketError::InvalidFeeSchedule(
+                format!("Treasury {} bps and burn {} bps must sum to 10000", self.treasury_bps, self.burn_bps)
+            ));
+        }
+        Ok(())
+    }
+
+    // The treasury takes the remainder so the parts always sum to `fee`
+    pub fn split(&self, fee: f64) -> FeeSplit {
+        let burn = fee * self.burn_bps as f64 / 10_000.0;
+        FeeSplit { treasury: fee - burn, burn }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeeSplit {
+    pub treasury: f64,
+    pub burn: f64,
+}
+
+impl FeeSplit {
+    pub fn total(&self) -> f64 {
+        self.treasury + self.burn
+    }
+}
+
+// Price of a single trade: its LMSR cost and the fee charged on top
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct TradeCost {
+    pub cost: f64,
+    pub fee: f64,
+}
+
+impl TradeCost {
+    pub fn total(&self) -> f64 {
+        self.cost + self.fee
+    }
+}
+
+// Treasury and burn amounts accumulated per market
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "FeeLedgerFields")]
+pub struct FeeLedger {
+    distribution: FeeDistribution,
+    markets: HashMap<String, FeeSplit>,
+}
+
+// Unchecked wire form of `FeeLedger`
+#[derive(Deserialize)]
+struct FeeLedgerFields {
+    distribution: FeeDistribution,
+    markets: HashMap<String, FeeSplit>,
+}
+
+impl TryFrom<FeeLedgerFields> for FeeLedger {
+    type Error = MarketError;
+
+    fn try_from(fields: FeeLedgerFields) -> Result<Self, Self::Error> {
+        let mut ledger = FeeLedger::new(fields.distribution)?;
+        for (market_id, totals) in fields.markets {
+            if [totals.treasury, totals.burn].iter().any(|amount| !amount.is_finite() || *amount < 0.0) {
+                return Err(MarketError::CalculationError(
+                    format!("Invalid fee totals {:?} for market {}", totals, market_id)
+                ));
+            }
+            ledger.markets.insert(market_id, totals);
+        }
+
+        Ok(ledger)
+    }
+}
+
+impl FeeLedger {
+    pub fn new(distribution: FeeDistribution) -> Result<Self, MarketError> {
+        distribution.validate()?;
+        Ok(FeeLedger { distribution, markets: HashMap::new() })
+    }
+
+    // Splits `fee` and adds it to the market's running totals; returns the
+    // split of this fee alone
+    pub fn record(&mut self, market_id: &str, fee: f64) -> Result<FeeSplit, MarketError> {
+        if !fee.is_finite() || fee < 0.0 {
+            return Err(MarketError::CalculationError(format!("Invalid fee amount {}", fee)));
+        }
+
+        let split = self.distribution.split(fee);
+        let totals = self.markets.entry(market_id.to_string()).or_default();
+        totals.treasury += split.treasury;
+        totals.burn += split.burn;
+
+        Ok(split)
+    }
+
+    // Totals recorded for `market_id`; zero if it has collected nothing
+    pub fn market(&self, market_id: &str) -> FeeSplit {
+        self.markets.get(market_id).copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{outcome_totals, Bet, MarketConfig, MarketError, OutcomeTotals, ProbabilityEngine};
+use rust_decimal::prelude::*;
+
+// Live market that keeps its outcome totals and partition sum between
+// bets, so each new bet costs O(1) amortized to record and O(num_outcomes)
+// to reprice instead of a full pass over the bet history.
+pub struct IncrementalMarket {
+    engine: ProbabilityEngine,
+    outcome_totals: OutcomeTotals,
+    seeds: OutcomeTotals,
+    // Per-outcome `b_i`, or None when adaptive liquidity rescales every
+    // term on each bet and the partition has to be rebuilt in full
+    liquidity: Option<Vec<f64>>,
+    partition: PartitionSum,
+}
+
+impl IncrementalMarket {
+    pub fn new(config: MarketConfig) -> Result<Self, MarketError> {
+        config.validate()?;
+        let outcome_totals = outcome_totals(&config, &[])?;
+        let liquidity = match (&config.adaptive_liquidity, &config.outcome_liquidity) {
+            (Some(_), _) => None,
+            (None, Some(outcome_liquidity)) => Some(outcome_liquidity.clone()),
+            (None, None) => Some(vec![config.liquidity_param; config.num_outcomes]),
+        };
+
+        let engine = ProbabilityEngine::new(config);
+        let partition = PartitionSum::new(engine.scaled_quantities(&outcome_totals)?);
+
+        Ok(IncrementalMarket {
+            engine,
+            seeds: outcome_totals.clone(),
+            outcome_totals,
+            liquidity,
+            partition,
+        })
+    }
+
+    // Records `bet` and returns the updated probabilities. A rejected bet
+    // leaves the market unchanged.
+    pub fn apply_bet(&mut self, bet: &Bet) -> Result<Vec<f64>, MarketError> {
+        self.record_bet(bet)?;
+        self.probabilities()
+    }
+
+    // Records `bet` without repricing every outcome. Updates the running
+    // partition sum in O(1) amortized, except under adaptive liquidity;
+    // read single outcomes back with `price`. A rejected bet leaves the
+    // market unchanged.
+    pub fn record_bet(&mut self, bet: &Bet) -> Result<(), MarketError> {
+        let liquidity = match &self.liquidity {
+            Some(liquidity) => liquidity,
+            None => {
+                let mut updated = self.outcome_totals.clone();
+                updated.add_bet(bet)?;
+                self.partition = PartitionSum::new(self.engine.scaled_quantities(&updated)?);
+                self.outcome_totals = updated;
+                return Ok(());
+            }
+        };
+
+        let outcome = bet.option_id;
+        self.outcome_totals.add_bet(bet)?;
+        let quantity = (self.outcome_totals[outcome] - self.seeds[outcome]).to_f64().unwrap_or(0.0);
+        self.partition.update(outcome, quantity / liquidity[outcome]);
+
+        Ok(())
+    }
+
+    // Current probabilities, recomputed exactly in O(num_outcomes)
+    pub fn probabilities(&self) -> Result<Vec<f64>, MarketError> {
+        let probabilities = self.engine.probabilities_from_totals(&self.outcome_totals)?;
+        Ok(probabilities.to_f64_vec())
+    }
+
+    // Price of a single outcome from the running partition sum, in O(1)
+    pub fn price(&self, outcome: usize) -> Result<f64, MarketError> {
+        self.partition.price(outcome).ok_or(MarketError::InvalidOutcomeIndex(outcome))
+    }
+
+    // Z = Σ exp(q_i / b_i), as `ProbabilityEngine::partition_function`
+    pub fn partition_function(&self) -> f64 {
+        self.partition.log_value().exp()
+    }
+}
+
+// Running Σ exp(s_i - shift) over scaled quantities `s_i`. The shift tracks
+// the largest term so nothing overflows; the sum is rebuilt from scratch
+// every `scaled.len()` updates to stop cancellation error accumulating.
+struct PartitionSum {
+    scaled: Vec<f64>,
+    shift: f64,
+    sum: f64,
+    updates: usize,
+}
+
+impl PartitionSum {
+    fn new(scaled: Vec<f64>) -> Self {
+        let mut partition = PartitionSum { scaled, shift: 0.0, sum: 0.0, updates: 0 };
+        partition.rebuild();
+        partition
+    }
+
+    fn rebuild(&mut self) {
+        self.shift = self.scaled.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        self.sum = self.scaled.iter().map(|s| (s - self.shift).exp()).sum();
+        self.updates = 0;
+    }
+
+    fn update(&mut self, outcome: usize, scaled: f64) {
+        // A new maximum re-bases the sum before its term is swapped in
+        if scaled > self.shift {
+            self.sum *= (self.shift - scaled).exp();
+            self.shift = scaled;
+        }
+
+        let previous = std::mem::replace(&mut self.scaled[outcome], scaled);
+        self.sum += (scaled - self.shift).exp() - (previous - self.shift).exp();
+        self.updates += 1;
+
+        if self.updates >= self.scaled.len() || !(self.sum.is_finite() && self.sum > 0.0) {
+            self.rebuild();
+        }
+    }
+
+    fn price(&self, outcome: usize) -> Option<f64> {
+        self.scaled.get(outcome).map(|s| (s - self.shift).exp() / self.sum)
+    }
+
+    fn log_value(&self) -> f64 {
+        self.shift + self.sum.ln()
+    }
+}
+
@@ -1,16 +1,114 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wasm_bindgen::prelude::*;
 
+mod lmsr;
 mod market_maker;
 mod risk_assessment;
 mod probability_engine;
+mod order_book;
+mod settlement;
+mod scoring;
+mod distributions;
+mod fees;
+mod incremental;
+mod combinatorial;
+mod oracle;
+mod outcomes;
+mod quote_engine;
 
 pub use market_maker::MarketMakerEngine;
 pub use risk_assessment::RiskAssessmentEngine;
 pub use probability_engine::ProbabilityEngine;
+pub use order_book::{FilledOrder, LimitOrder, LimitOrderBook};
+pub use settlement::{PayoutStatus, PositionPayout, SettlementEngine, SettlementLedger, SettlementOutcome};
+pub use scoring::{brier_decomposition, log_loss, BrierDecomposition};
+pub use distributions::{cross_entropy, kl_divergence};
+pub use fees::{FeeDistribution, FeeLedger, FeeSchedule, FeeSplit, FeeTier, TradeCost};
+pub use incremental::IncrementalMarket;
+pub use combinatorial::joint_probability;
+pub use oracle::{ManualOracle, OracleError, OutcomeOracle};
+pub use outcomes::{OutcomeTotals, Probabilities};
+pub use quote_engine::{QuoteEngine, QuoteRefresh};
+
+// Floor applied to probabilities before taking logarithms. 1e-9 keeps
+// ln(p) above roughly -20.7, so entropy-based terms stay finite and
+// bounded even when an outcome is all but impossible.
+pub(crate) const PROBABILITY_EPSILON: Decimal = Decimal::from_parts(1, 0, 0, false, 9);
+pub(crate) const PROBABILITY_EPSILON_F64: f64 = 1e-9;
+
+// Largest value and its index; ties resolve to the lowest index
+pub(crate) fn max_with_index(values: &[Decimal]) -> Option<(usize, Decimal)> {
+    values.iter()
+        .copied()
+        .enumerate()
+        .fold(None, |best, (index, value)| match best {
+            Some((_, best_value)) if value <= best_value => best,
+            _ => Some((index, value)),
+        })
+}
+
+// Amount backing each outcome: an equal liquidity seed of b/n plus every
+// bet placed on it. Shared by all engines so they agree on market state.
+pub fn outcome_totals(config: &MarketConfig, bets: &[Bet]) -> Result<OutcomeTotals, MarketError> {
+    if config.num_outcomes == 0 {
+        return Err(MarketError::InsufficientData("Market has no outcomes".to_string()));
+    }
+    let liquidity_param = Decimal::from_f64(config.liquidity_param)
+        .filter(|liquidity| *liquidity > Decimal::ZERO)
+        .ok_or_else(|| MarketError::InvalidLiquidity("Invalid liquidity parameter".to_string()))?;
+    let initial_liquidity = liquidity_param / Decimal::from(config.num_outcomes);
+
+    let mut totals = OutcomeTotals::new(vec![initial_liquidity; config.num_outcomes])?;
+    for bet in bets {
+        totals.add_bet(bet)?;
+    }
+
+    Ok(totals)
+}
+
+// LMSR cost of each bet applied in order. A bet of `amount` shares on
+// outcome k costs b_k·(L(q'/b) - L(q/b)) with L = ln Σ exp, the integral
+// of k's price over the trade; under a single `b` that is C(q') - C(q).
+// Adaptive markets price each bet at the depth reached before it.
+pub(crate) fn trade_costs(config: &MarketConfig, bets: &[Bet]) -> Result<Vec<f64>, MarketError> {
+    config.validate()?;
+
+    let mut quantities = vec![0.0; config.num_outcomes];
+    let mut volume = 0.0;
+    let mut costs = Vec::with_capacity(bets.len());
+    for bet in bets {
+        if bet.option_id >= quantities.len() {
+            return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
+        }
+        if !bet.amount.is_finite() || bet.amount < 0.0 {
+            return Err(MarketError::CalculationError(format!("Invalid bet amount {}", bet.amount)));
+        }
+
+        let liquidity = match &config.outcome_liquidity {
+            Some(outcome_liquidity) => outcome_liquidity.clone(),
+            None => vec![config.effective_liquidity(volume); quantities.len()],
+        };
+        if let Some(b) = liquidity.iter().find(|b| !b.is_finite() || **b <= 0.0) {
+            return Err(MarketError::InvalidLiquidity(format!("Effective liquidity {} is not positive", b)));
+        }
+        let log_partition = |quantities: &[f64]| {
+            let scaled: Vec<f64> = quantities.iter().zip(&liquidity).map(|(q, b)| q / b).collect();
+            lmsr::cost(&scaled, 1.0)
+        };
+
+        let before = log_partition(&quantities);
+        quantities[bet.option_id] += bet.amount;
+        volume += bet.amount;
+        // Buying shares never lowers the cost; clamp rounding noise
+        costs.push((liquidity[bet.option_id] * (log_partition(&quantities) - before)).max(0.0));
+    }
+
+    Ok(costs)
+}
 
 // Error type for market operations
 #[derive(Error, Debug)]
@@ -23,8 +121,20 @@ pub enum MarketError {
     CalculationError(String),
     #[error("Insufficient data: {0}")]
     InsufficientData(String),
+    #[error("Invalid settlement: {0}")]
+    InvalidSettlement(String),
+    #[error("Invalid market state: {0}")]
+    InvalidState(String),
+    #[error("Invalid fee schedule: {0}")]
+    InvalidFeeSchedule(String),
+    #[error("Oracle error: {0}")]
+    Oracle(#[from] OracleError),
 }
 
+// `amount` is the number of shares bought, so each bet raises its outcome's
+// quantity q_i by `amount`; what it costs follows from the LMSR (see
+// `PredictionMarketEngine::total_collateral`). Parimutuel markets have no
+// prices, so there a share costs 1 and `amount` is the stake.
 #[wasm_bindgen]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bet {
@@ -42,6 +152,72 @@ pub enum MarketType {
     Scalar,
 }
 
+// A bet with the time it was placed, for time-based risk measures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedBet {
+    pub bet: Bet,
+    pub placed_at: DateTime<Utc>,
+}
+
+// Volume accumulation over the latest window and its change from the window
+// before it. Units are volume per second and volume per second squared.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct BetVelocity {
+    pub rate: f64,
+    pub acceleration: f64,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum PayoutModel {
+    // Each winning share pays a fixed 1
+    #[default]
+    Lmsr,
+    // Winners split the pool pro rata to their stake
+    Parimutuel,
+}
+
+// Liquidity that deepens with the market: b = liquidity_param + k·volume
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct AdaptiveConfig {
+    // The `k` in b + k·volume
+    pub volume_factor: f64,
+}
+
+// Units probabilities are reported in across the wasm API
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum ProbabilityFormat {
+    // 0-1
+    #[default]
+    Raw,
+    // 0-100
+    Percentage,
+    // 0-10000
+    BasisPoints,
+}
+
+impl ProbabilityFormat {
+    // Every format is rounded to the same resolution (1e-6 of the
+    // probability) before scaling, so all units report the same value
+    pub fn render(self, probability: Decimal) -> f64 {
+        let probability = probability.round_dp(6);
+        let rendered = match self {
+            ProbabilityFormat::Raw => probability,
+            ProbabilityFormat::Percentage => probability * Decimal::ONE_HUNDRED,
+            ProbabilityFormat::BasisPoints => probability * Decimal::from(10_000),
+        };
+        rendered.to_f64().unwrap_or(0.0)
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
 #[wasm_bindgen]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketConfig {
@@ -51,15 +227,130 @@ pub struct MarketConfig {
     pub num_outcomes: usize,
     #[wasm_bindgen(getter)]
     pub market_type: MarketType,
+    #[serde(default)]
+    pub maker_rebate_bps: u32,
+    #[serde(default)]
+    pub taker_fee_bps: u32,
+    // Spread widening per unit of realized volatility (the `k` in 1 + k·vol)
+    #[serde(default = "default_volatility_sensitivity")]
+    pub volatility_sensitivity: f64,
+    // Upper bound on the maker's relative half-spread
+    #[serde(default = "default_max_half_spread")]
+    pub max_half_spread: f64,
+    #[serde(default)]
+    pub payout_model: PayoutModel,
+    // Fee taken from the pool when a parimutuel market settles
+    #[serde(default)]
+    pub fee_bps: u32,
+    // Optional per-outcome liquidity `b_i`; the scalar `liquidity_param`
+    // applies to every outcome when absent
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub outcome_liquidity: Option<Vec<f64>>,
+    // When set, `b` grows with total volume instead of staying fixed
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub adaptive_liquidity: Option<AdaptiveConfig>,
+    // Volume-tiered trading fees; the flat `taker_fee_bps` applies when absent
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub fee_schedule: Option<FeeSchedule>,
+}
+
+// Liquidity used when a caller doesn't pick one. Any positive, finite `b`
+// is accepted, but below roughly 1 a single unit bet swings prices to the
+// extremes, so markets should normally sit well above that.
+pub const DEFAULT_LIQUIDITY_PARAM: f64 = 100.0;
+
+fn default_volatility_sensitivity() -> f64 {
+    1.0
+}
+
+fn default_max_half_spread() -> f64 {
+    0.25
+}
+
+impl MarketConfig {
+    pub fn new(liquidity_param: f64, num_outcomes: usize, market_type: MarketType) -> Self {
+        MarketConfig {
+            liquidity_param,
+            num_outcomes,
+            market_type,
+            maker_rebate_bps: 0,
+            taker_fee_bps: 0,
+            volatility_sensitivity: default_volatility_sensitivity(),
+            max_half_spread: default_max_half_spread(),
+            payout_model: PayoutModel::Lmsr,
+            fee_bps: 0,
+            outcome_liquidity: None,
+            adaptive_liquidity: None,
+            fee_schedule: None,
+        }
+    }
+
+    // Trading fee for a user with `user_volume` of cumulative volume
+    pub fn trade_fee_bps(&self, user_volume: f64) -> u32 {
+        match &self.fee_schedule {
+            Some(schedule) => schedule.fee_for(user_volume),
+            None => self.taker_fee_bps,
+        }
+    }
+
+    // Liquidity in force once `total_volume` has been traded
+    pub fn effective_liquidity(&self, total_volume: f64) -> f64 {
+        match &self.adaptive_liquidity {
+            Some(adaptive) => self.liquidity_param + adaptive.volume_factor * total_volume,
+            None => self.liquidity_param,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), MarketError> {
+        if !self.liquidity_param.is_finite() || self.liquidity_param <= 0.0 {
+            return Err(MarketError::InvalidLiquidity(
+                format!("Liquidity must be positive and finite, got {}", self.liquidity_param)
+            ));
+        }
+
+        if let Some(outcome_liquidity) = &self.outcome_liquidity {
+            if outcome_liquidity.len() != self.num_outcomes {
+                return Err(MarketError::InvalidLiquidity(format!(
+                    "Expected {} per-outcome liquidity values, got {}",
+                    self.num_outcomes,
+                    outcome_liquidity.len()
+                )));
+            }
+            if let Some(b) = outcome_liquidity.iter().find(|b| !b.is_finite() || **b <= 0.0) {
+                return Err(MarketError::InvalidLiquidity(
+                    format!("Per-outcome liquidity must be positive and finite, got {}", b)
+                ));
+            }
+        }
+
+        if let Some(adaptive) = &self.adaptive_liquidity {
+            if !adaptive.volume_factor.is_finite() || adaptive.volume_factor < 0.0 {
+                return Err(MarketError::InvalidLiquidity(
+                    format!("Adaptive volume factor must be non-negative and finite, got {}", adaptive.volume_factor)
+                ));
+            }
+            if self.outcome_liquidity.is_some() {
+                return Err(MarketError::InvalidLiquidity(
+                    "Adaptive and per-outcome liquidity cannot be combined".to_string()
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarketMakingStrategy {
     bid_prices: Vec<f64>,
     ask_prices: Vec<f64>,
     spread: f64,
     recommended_liquidity: f64,
+    net_edge: f64,
 }
 
 #[wasm_bindgen]
@@ -83,16 +374,24 @@ impl MarketMakingStrategy {
     pub fn recommended_liquidity(&self) -> f64 {
         self.recommended_liquidity
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn net_edge(&self) -> f64 {
+        self.net_edge
+    }
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarketRiskProfile {
     probabilities: Vec<f64>,
     entropy: f64,
     concentration: f64,
     expected_volatility: f64,
     liquidity_risk: f64,
+    // Only known when the bets carry timestamps (see `assess_risk_timed`)
+    #[serde(default)]
+    velocity: Option<BetVelocity>,
 }
 
 #[wasm_bindgen]
@@ -121,6 +420,88 @@ impl MarketRiskProfile {
     pub fn liquidity_risk(&self) -> f64 {
         self.liquidity_risk
     }
+
+    // Acceleration of bet volume; a spike can signal an imminent resolution
+    #[wasm_bindgen(getter)]
+    pub fn bet_acceleration(&self) -> Option<f64> {
+        self.velocity.map(|velocity| velocity.acceleration)
+    }
+}
+
+impl MarketRiskProfile {
+    pub fn velocity(&self) -> Option<BetVelocity> {
+        self.velocity
+    }
+}
+
+// A bet rejected at the wasm boundary, either unreadable or invalid
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BetParseError {
+    pub index: usize,
+    pub reason: String,
+}
+
+// Result of lenient parsing: the usable bets plus the ones that were skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedBets {
+    pub bets: Vec<Bet>,
+    pub rejected: Vec<BetParseError>,
+}
+
+impl ParsedBets {
+    // Keeps every readable bet and records the index of each one that wasn't
+    pub fn lenient<E: std::fmt::Display>(bets: impl IntoIterator<Item = Result<Bet, E>>) -> Self {
+        let mut parsed = ParsedBets { bets: Vec::new(), rejected: Vec::new() };
+        for (index, bet) in bets.into_iter().enumerate() {
+            match bet {
+                Ok(bet) => parsed.bets.push(bet),
+                Err(e) => parsed.rejected.push(BetParseError { index, reason: e.to_string() }),
+            }
+        }
+
+        parsed
+    }
+
+    // Fails on the first unreadable bet
+    pub fn strict<E: std::fmt::Display>(bets: impl IntoIterator<Item = Result<Bet, E>>) -> Result<Vec<Bet>, BetParseError> {
+        bets.into_iter()
+            .enumerate()
+            .map(|(index, bet)| bet.map_err(|e| BetParseError { index, reason: e.to_string() }))
+            .collect()
+    }
+}
+
+// Combined result of `analyzeMarket`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketAnalysis {
+    pub probabilities: Vec<f64>,
+    pub strategy: MarketMakingStrategy,
+    pub risk: MarketRiskProfile,
+}
+
+// Serializable snapshot of a market, used as a reproducible fixture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketState {
+    pub config: MarketConfig,
+    pub bets: Vec<Bet>,
+    // LMSR partition function at export time, for diagnosing pricing bugs.
+    // Informational only; ignored when the state is loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_function: Option<f64>,
+}
+
+impl MarketState {
+    pub fn from_json(json: &str) -> Result<Self, MarketError> {
+        let state: MarketState = serde_json::from_str(json)
+            .map_err(|e| MarketError::InvalidState(e.to_string()))?;
+
+        if let Some(bet) = state.bets.iter().find(|b| b.option_id >= state.config.num_outcomes) {
+            return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
+        }
+        state.config.validate()?;
+
+        Ok(state)
+    }
 }
 
 #[wasm_bindgen]
@@ -138,39 +519,51 @@ impl PredictionMarketEngine {
         liquidity_param: f64,
         num_outcomes: usize,
         market_type: MarketType
-    ) -> Self {
-        let config = MarketConfig {
-            liquidity_param,
-            num_outcomes,
-            market_type,
+    ) -> Result<PredictionMarketEngine, JsValue> {
+        Self::try_new(liquidity_param, num_outcomes, market_type)
+            .map_err(|e| JsValue::from_str(&format!("Invalid market config: {:?}", e)))
+    }
+
+    #[wasm_bindgen(js_name = withDefaultLiquidity)]
+    pub fn with_default_liquidity(num_outcomes: usize, market_type: MarketType) -> PredictionMarketEngine {
+        Self::from_config(MarketConfig::new(DEFAULT_LIQUIDITY_PARAM, num_outcomes, market_type))
+    }
+
+    // Parses bets, skipping malformed entries instead of failing the batch.
+    // The skipped entries are reported under `rejected`.
+    #[wasm_bindgen(js_name = parseBetsLenient)]
+    pub fn parse_bets_lenient(bets: Vec<JsValue>) -> Result<JsValue, JsValue> {
+        let parsed = ParsedBets::lenient(bets.into_iter().map(serde_wasm_bindgen::from_value::<Bet>));
+
+        serde_wasm_bindgen::to_value(&parsed)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {:?}", e)))
+    }
+
+    // Checks a whole batch up front. On failure returns every problem found
+    // as a list of `BetParseError`, not just the first.
+    #[wasm_bindgen(js_name = validateBets)]
+    pub fn validate_bets(&self, bets: Vec<JsValue>) -> Result<(), JsValue> {
+        let problems = match self.validate_parsed(bets.into_iter().map(serde_wasm_bindgen::from_value::<Bet>)) {
+            Ok(()) => return Ok(()),
+            Err(problems) => problems,
         };
 
-        PredictionMarketEngine {
-            config: config.clone(),
-            probability_engine: ProbabilityEngine::new(config.clone()),
-            market_maker: MarketMakerEngine::new(config.clone()),
-            risk_assessment: RiskAssessmentEngine::new(config),
-        }
+        let problems = serde_wasm_bindgen::to_value(&problems)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {:?}", e)))?;
+        Err(problems)
     }
 
     #[wasm_bindgen(js_name = calculateProbabilities)]
-    pub fn calculate_probabilities(&self, bets: Vec<JsValue>) -> Result<Vec<f64>, JsValue> {
-        let bets: Vec<Bet> = bets.into_iter()
-            .map(|bet_js| serde_wasm_bindgen::from_value(bet_js))
-            .collect::<Result<Vec<Bet>, _>>()
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse bets: {:?}", e)))?;
-
-        self.probability_engine.calculate_probabilities(&bets)
-            .map(|probs| probs.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect())
+    pub fn calculate_probabilities(&self, bets: Vec<JsValue>, format: Option<ProbabilityFormat>) -> Result<Vec<f64>, JsValue> {
+        let bets = Self::parse_bets(bets)?;
+
+        self.formatted_probabilities(&bets, format.unwrap_or_default())
             .map_err(|e| JsValue::from_str(&format!("Probability calculation error: {:?}", e)))
     }
 
     #[wasm_bindgen(js_name = simulateMarketMaking)]
     pub fn simulate_market_making(&self, bets: Vec<JsValue>) -> Result<JsValue, JsValue> {
-        let bets: Vec<Bet> = bets.into_iter()
-            .map(|bet_js| serde_wasm_bindgen::from_value(bet_js))
-            .collect::<Result<Vec<Bet>, _>>()
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse bets: {:?}", e)))?;
+        let bets = Self::parse_bets(bets)?;
 
         let result = self.market_maker.simulate_strategy(&bets)
             .map_err(|e| JsValue::from_str(&format!("Market making error: {:?}", e)))?;
@@ -181,10 +574,7 @@ impl PredictionMarketEngine {
 
     #[wasm_bindgen(js_name = assessMarketRisk)]
     pub fn assess_market_risk(&self, bets: Vec<JsValue>) -> Result<JsValue, JsValue> {
-        let bets: Vec<Bet> = bets.into_iter()
-            .map(|bet_js| serde_wasm_bindgen::from_value(bet_js))
-            .collect::<Result<Vec<Bet>, _>>()
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse bets: {:?}", e)))?;
+        let bets = Self::parse_bets(bets)?;
 
         let result = self.risk_assessment.assess_risk(&bets)
             .map_err(|e| JsValue::from_str(&format!("Risk assessment error: {:?}", e)))?;
@@ -193,15 +583,260 @@ impl PredictionMarketEngine {
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {:?}", e)))
     }
 
+    #[wasm_bindgen(js_name = analyzeMarket)]
+    pub fn analyze_market(&self, bets: Vec<JsValue>) -> Result<JsValue, JsValue> {
+        let bets = Self::parse_bets(bets)?;
+
+        let result = self.analyze(&bets)
+            .map_err(|e| JsValue::from_str(&format!("Market analysis error: {:?}", e)))?;
+
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {:?}", e)))
+    }
+
     #[wasm_bindgen(js_name = calculatePrice)]
-    pub fn calculate_price(&self, bets: Vec<JsValue>, outcome_index: usize) -> Result<f64, JsValue> {
-        let bets: Vec<Bet> = bets.into_iter()
-            .map(|bet_js| serde_wasm_bindgen::from_value(bet_js))
-            .collect::<Result<Vec<Bet>, _>>()
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse bets: {:?}", e)))?;
-
-        self.probability_engine.calculate_price(&bets, outcome_index)
-            .map(|price| price.to_f64().unwrap_or(0.0))
+    pub fn calculate_price(
+        &self,
+        bets: Vec<JsValue>,
+        outcome_index: usize,
+        format: Option<ProbabilityFormat>
+    ) -> Result<f64, JsValue> {
+        let bets = Self::parse_bets(bets)?;
+
+        self.formatted_price(&bets, outcome_index, format.unwrap_or_default())
             .map_err(|e| JsValue::from_str(&format!("Price calculation error: {:?}", e)))
     }
-}
\ No newline at end of file
+}
+
+impl PredictionMarketEngine {
+    // Probabilities, maker quotes and risk profile in one pass. Every engine
+    // prices off the same LMSR probabilities, computed once.
+    pub fn analyze(&self, bets: &[Bet]) -> Result<MarketAnalysis, MarketError> {
+        let probabilities = self.probability_engine.calculate_probabilities(bets)?;
+
+        Ok(MarketAnalysis {
+            probabilities: probabilities.to_f64_vec(),
+            strategy: self.market_maker.strategy_from_probabilities(&probabilities),
+            risk: self.risk_assessment.profile_from_probabilities(&probabilities, bets),
+        })
+    }
+
+    pub fn formatted_probabilities(&self, bets: &[Bet], format: ProbabilityFormat) -> Result<Vec<f64>, MarketError> {
+        let probabilities = self.probability_engine.calculate_probabilities(bets)?;
+        Ok(probabilities.iter().map(|&p| format.render(p)).collect())
+    }
+
+    pub fn formatted_price(&self, bets: &[Bet], outcome_index: usize, format: ProbabilityFormat) -> Result<f64, MarketError> {
+        Ok(format.render(self.probability_engine.calculate_price(bets, outcome_index)?))
+    }
+
+    // Validates a batch of per-entry parse results, reporting every
+    // unreadable or invalid bet in index order
+    pub fn validate_parsed<E: std::fmt::Display>(
+        &self,
+        bets: impl IntoIterator<Item = Result<Bet, E>>,
+    ) -> Result<(), Vec<BetParseError>> {
+        let mut problems = Vec::new();
+        for (index, bet) in bets.into_iter().enumerate() {
+            match bet {
+                Ok(bet) => problems.extend(self.bet_problems(index, &bet)),
+                Err(e) => problems.push(BetParseError { index, reason: e.to_string() }),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    pub fn try_new(liquidity_param: f64, num_outcomes: usize, market_type: MarketType) -> Result<Self, MarketError> {
+        Self::try_from_config(MarketConfig::new(liquidity_param, num_outcomes, market_type))
+    }
+
+    pub fn try_from_config(config: MarketConfig) -> Result<Self, MarketError> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    pub fn config(&self) -> &MarketConfig {
+        &self.config
+    }
+
+    // Captures the config and bets as a JSON fixture for bug reports
+    pub fn export_state(&self, bets: &[Bet]) -> String {
+        let state = MarketState {
+            config: self.config.clone(),
+            bets: bets.to_vec(),
+            partition_function: self.probability_engine.partition_function(bets).ok(),
+        };
+
+        serde_json::to_string_pretty(&state).expect("Market state is always serializable")
+    }
+
+    // Rebuilds an engine from a fixture produced by `export_state`
+    pub fn from_state(json: &str) -> Result<Self, MarketError> {
+        let state = MarketState::from_json(json)?;
+        Ok(Self::from_config(state.config))
+    }
+
+    // Collateral paid in by `bets` applied in order: the sum of each bet's
+    // LMSR cost under the effective liquidity. With a single fixed `b` it
+    // telescopes to C(q_final) - C(0).
+    pub fn total_collateral(&self, bets: &[Bet]) -> Result<f64, MarketError> {
+        Ok(trade_costs(&self.config, bets)?.iter().sum())
+    }
+
+    // What placing `bet` after `bets` costs a user who has already traded
+    // `user_volume`: its LMSR cost plus the fee at the user's tier. The fee
+    // is charged on the size of the trade, so it is never negative, even
+    // for a sell whose cost is.
+    pub fn trade_cost(&self, bets: &[Bet], bet: &Bet, user_volume: f64) -> Result<TradeCost, MarketError> {
+        let mut placed = bets.to_vec();
+        placed.push(bet.clone());
+        let cost = trade_costs(&self.config, &placed)?.pop().unwrap_or(0.0);
+        let fee = cost.abs() * self.config.trade_fee_bps(user_volume) as f64 / 10_000.0;
+
+        Ok(TradeCost { cost, fee })
+    }
+
+    // As `trade_cost`, also booking the fee's treasury and burn split
+    // against `market_id` in `ledger`
+    pub fn record_trade_fee(
+        &self,
+        market_id: &str,
+        bets: &[Bet],
+        bet: &Bet,
+        user_volume: f64,
+        ledger: &mut FeeLedger
+    ) -> Result<TradeCost, MarketError> {
+        let trade = self.trade_cost(bets, bet, user_volume)?;
+        ledger.record(market_id, trade.fee)?;
+        Ok(trade)
+    }
+
+    // For each possible winner: (outcome, current market probability, payout)
+    // for a holder of `positions`, given as (outcome, shares). Each share of
+    // the winning outcome pays 1.
+    pub fn payout_distribution(&self, bets: &[Bet], positions: &[(usize, f64)]) -> Result<Vec<(usize, f64, f64)>, MarketError> {
+        let probabilities = self.probability_engine.calculate_probabilities(bets)?;
+
+        let mut payouts = vec![0.0; self.config.num_outcomes];
+        for &(outcome, shares) in positions {
+            if outcome >= payouts.len() {
+                return Err(MarketError::InvalidOutcomeIndex(outcome));
+            }
+            if !shares.is_finite() || shares < 0.0 {
+                return Err(MarketError::CalculationError(format!("Invalid share count {}", shares)));
+            }
+            payouts[outcome] += shares;
+        }
+
+        Ok(probabilities.to_f64_vec()
+            .into_iter()
+            .zip(payouts)
+            .enumerate()
+            .map(|(outcome, (probability, payout))| (outcome, probability, payout))
+            .collect())
+    }
+
+    // Pools two books on the same event into one deeper market. Liquidity
+    // adds (b = b_other + b_self, per outcome when configured) and so do the
+    // quantities, making each outcome's scaled quantity q/b the
+    // liquidity-weighted mean of the two books'. In a binary market with a
+    // single `b` the merged price therefore lies between the inputs', closer
+    // to the deeper book. Returns the merged config, taking every other
+    // setting from `self_config`, and one bet per outcome with its pooled
+    // quantity.
+    pub fn merge_markets(
+        other_config: &MarketConfig,
+        other_bets: &[Bet],
+        self_config: &MarketConfig,
+        self_bets: &[Bet],
+    ) -> Result<(MarketConfig, Vec<Bet>), MarketError> {
+        other_config.validate()?;
+        self_config.validate()?;
+        if other_config.num_outcomes != self_config.num_outcomes || other_config.market_type != self_config.market_type {
+            return Err(MarketError::CalculationError(format!(
+                "Cannot merge a {}-outcome {:?} market into a {}-outcome {:?} market",
+                other_config.num_outcomes, other_config.market_type, self_config.num_outcomes, self_config.market_type
+            )));
+        }
+        // b = b₀ + k·volume only pools when both books grow at the same rate
+        if other_config.adaptive_liquidity != self_config.adaptive_liquidity {
+            return Err(MarketError::InvalidLiquidity("Cannot merge markets with different adaptive liquidity".to_string()));
+        }
+
+        let outcome_liquidity = match (&other_config.outcome_liquidity, &self_config.outcome_liquidity) {
+            (None, None) => None,
+            (other, this) => {
+                let per_outcome = |config: &MarketConfig, liquidity: &Option<Vec<f64>>| {
+                    liquidity.clone().unwrap_or_else(|| vec![config.liquidity_param; config.num_outcomes])
+                };
+                Some(per_outcome(other_config, other).iter()
+                    .zip(per_outcome(self_config, this))
+                    .map(|(other, this)| other + this)
+                    .collect())
+            }
+        };
+        let config = MarketConfig {
+            liquidity_param: other_config.liquidity_param + self_config.liquidity_param,
+            outcome_liquidity,
+            ..self_config.clone()
+        };
+
+        let mut quantities = vec![0.0; config.num_outcomes];
+        for bet in other_bets.iter().chain(self_bets) {
+            if bet.option_id >= config.num_outcomes {
+                return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
+            }
+            if !bet.amount.is_finite() || bet.amount < 0.0 {
+                return Err(MarketError::CalculationError(format!("Invalid bet amount {}", bet.amount)));
+            }
+            quantities[bet.option_id] += bet.amount;
+        }
+
+        let bets = quantities.into_iter()
+            .enumerate()
+            .filter(|&(_, amount)| amount > 0.0)
+            .map(|(option_id, amount)| Bet { option_id, amount })
+            .collect();
+
+        Ok((config, bets))
+    }
+
+    fn bet_problems(&self, index: usize, bet: &Bet) -> Vec<BetParseError> {
+        let mut problems = Vec::new();
+        if bet.option_id >= self.config.num_outcomes {
+            problems.push(BetParseError {
+                index,
+                reason: format!("Outcome {} out of range for {} outcomes", bet.option_id, self.config.num_outcomes),
+            });
+        }
+        if !bet.amount.is_finite() || bet.amount < 0.0 {
+            problems.push(BetParseError { index, reason: format!("Invalid bet amount {}", bet.amount) });
+        }
+
+        problems
+    }
+
+    // Strict parsing: the first malformed bet fails the call with a
+    // serialized `BetParseError`
+    fn parse_bets(bets: Vec<JsValue>) -> Result<Vec<Bet>, JsValue> {
+        ParsedBets::strict(bets.into_iter().map(serde_wasm_bindgen::from_value::<Bet>))
+            .map_err(|error| {
+                serde_wasm_bindgen::to_value(&error)
+                    .unwrap_or_else(|_| JsValue::from_str(&format!("Failed to parse bet {}: {}", error.index, error.reason)))
+            })
+    }
+
+    fn from_config(config: MarketConfig) -> Self {
+        PredictionMarketEngine {
+            config: config.clone(),
+            probability_engine: ProbabilityEngine::new(config.clone()),
+            market_maker: MarketMakerEngine::new(config.clone()),
+            risk_assessment: RiskAssessmentEngine::new(config),
+        }
+    }
+}
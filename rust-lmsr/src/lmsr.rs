@@ -0,0 +1,48 @@
+// Core LMSR cost function helpers shared by the engines.
+//
+// All functions work on raw outcome quantities `q_i` and the liquidity
+// parameter `b`. Exponentials are taken over differences between
+// quantities so large markets don't overflow.
+
+// Instantaneous prices p_i = exp(q_i / b) / Σ exp(q_j / b)
+pub(crate) fn prices(quantities: &[f64], liquidity: f64) -> Vec<f64> {
+    let max = quantities.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = quantities.iter()
+        .map(|&q| ((q - max) / liquidity).exp())
+        .collect();
+    let sum: f64 = exps.iter().sum();
+
+    exps.iter().map(|e| e / sum).collect()
+}
+
+// Cost function C(q) = b·ln Σ exp(q_i / b), factored around the largest
+// quantity so large markets don't overflow
+pub(crate) fn cost(quantities: &[f64], liquidity: f64) -> f64 {
+    let max = quantities.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let sum: f64 = quantities.iter()
+        .map(|&q| ((q - max) / liquidity).exp())
+        .sum();
+
+    max + liquidity * sum.ln()
+}
+
+// Quantity outcome `outcome` must reach for its price to equal `target`,
+// holding all other quantities fixed. None if no other outcome exists.
+pub(crate) fn quantity_for_price(quantities: &[f64], liquidity: f64, outcome: usize, target: f64) -> Option<f64> {
+    let others: Vec<f64> = quantities.iter()
+        .enumerate()
+        .filter(|&(i, _)| i != outcome)
+        .map(|(_, &q)| q)
+        .collect();
+    let max_other = others.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if !max_other.is_finite() {
+        return None;
+    }
+
+    let log_sum_others = max_other / liquidity + others.iter()
+        .map(|&q| ((q - max_other) / liquidity).exp())
+        .sum::<f64>()
+        .ln();
+
+    Some(liquidity * ((target / (1.0 - target)).ln() + log_sum_others))
+}
@@ -1,82 +1,406 @@
-use crate::{Bet, MarketConfig, MarketError, MarketMakingStrategy};
+use crate::{lmsr, Bet, MarketConfig, MarketError, MarketMakingStrategy, Probabilities, ProbabilityEngine, Side, PROBABILITY_EPSILON};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
+// Relative distance of bid/ask from the market price in calm markets
+const BASE_HALF_SPREAD: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
+// Relative shift of the quoted mid per unit of liquidity `b` the maker is
+// long (or short) an outcome versus its average holding
+const INVENTORY_SKEW: f64 = 0.1;
+
+// Largest relative shift inventory may apply to a mid
+const MAX_INVENTORY_SKEW: f64 = 0.5;
+
+// Bisection steps when solving for volume under adaptive liquidity; enough
+// to narrow any bracket to f64 resolution
+const VOLUME_SEARCH_STEPS: usize = 128;
+
 pub struct MarketMakerEngine {
     config: MarketConfig,
+    // Quotes are centred on the same LMSR prices the probability engine reports
+    probability_engine: ProbabilityEngine,
 }
 
 impl MarketMakerEngine {
     pub fn new(config: MarketConfig) -> Self {
-        MarketMakerEngine { config }
+        let probability_engine = ProbabilityEngine::new(config.clone());
+        MarketMakerEngine { config, probability_engine }
     }
 
     pub fn simulate_strategy(&self, bets: &[Bet]) -> Result<MarketMakingStrategy, MarketError> {
         // Calculate current market probabilities
         let probabilities = self.calculate_market_probabilities(bets)?;
 
+        Ok(self.strategy_from_probabilities(&probabilities))
+    }
+
+    // Widens the base spread to base·(1 + k·vol), capped at `max_half_spread`
+    pub fn simulate_strategy_with_volatility(&self, bets: &[Bet], realized_vol: f64) -> Result<MarketMakingStrategy, MarketError> {
+        let half_spread = self.volatility_half_spread(realized_vol)?;
+        let probabilities = self.calculate_market_probabilities(bets)?;
+
+        Ok(self.strategy_with_half_spread(&probabilities, half_spread))
+    }
+
+    // Volatility-widened quotes re-centered for the maker's `inventory`: the
+    // mid of an outcome held above the average is lowered by
+    // INVENTORY_SKEW per `b` of excess (and raised when held below), so
+    // fills tend to flatten the book
+    pub fn simulate_strategy_with_inventory(
+        &self,
+        bets: &[Bet],
+        inventory: &[f64],
+        realized_vol: f64,
+    ) -> Result<MarketMakingStrategy, MarketError> {
+        if inventory.len() != self.config.num_outcomes {
+            return Err(MarketError::CalculationError(
+                format!("Inventory has {} outcomes, expected {}", inventory.len(), self.config.num_outcomes)
+            ));
+        }
+        if let Some(shares) = inventory.iter().find(|shares| !shares.is_finite()) {
+            return Err(MarketError::CalculationError(format!("Invalid inventory {}", shares)));
+        }
+
+        let half_spread = self.volatility_half_spread(realized_vol)?;
+        let probabilities = self.calculate_market_probabilities(bets)?;
+        let liquidity = self.config.effective_liquidity(self.outcome_quantities(bets)?.iter().sum());
+        if !liquidity.is_finite() || liquidity <= 0.0 {
+            return Err(MarketError::InvalidLiquidity(liquidity.to_string()));
+        }
+
+        let mean = inventory.iter().sum::<f64>() / inventory.len() as f64;
+        let mids: Vec<Decimal> = probabilities.iter()
+            .zip(inventory)
+            .map(|(&p, shares)| {
+                let skew = (INVENTORY_SKEW * (shares - mean) / liquidity).clamp(-MAX_INVENTORY_SKEW, MAX_INVENTORY_SKEW);
+                p * Decimal::from_f64(1.0 - skew).unwrap_or(Decimal::ONE)
+            })
+            .collect();
+
+        Ok(self.strategy_with_half_spread(&mids, half_spread))
+    }
+
+    // Base half spread widened to base·(1 + k·vol), capped at `max_half_spread`
+    fn volatility_half_spread(&self, realized_vol: f64) -> Result<Decimal, MarketError> {
+        if !realized_vol.is_finite() || realized_vol < 0.0 {
+            return Err(MarketError::CalculationError(
+                format!("Invalid realized volatility {}", realized_vol)
+            ));
+        }
+
+        let scaling = Decimal::from_f64(1.0 + self.config.volatility_sensitivity * realized_vol)
+            .ok_or_else(|| MarketError::CalculationError("Invalid volatility scaling".to_string()))?;
+        let max_half_spread = Decimal::from_f64(self.config.max_half_spread)
+            .unwrap_or(BASE_HALF_SPREAD)
+            .max(BASE_HALF_SPREAD);
+
+        Ok((BASE_HALF_SPREAD * scaling).min(max_half_spread))
+    }
+
+    // Additional volume on `outcome_index` needed to move its LMSR price to
+    // `target_prob`, under the same liquidity the probability engine prices with
+    pub fn volume_to_reach(&self, bets: &[Bet], outcome_index: usize, target_prob: f64) -> Result<f64, MarketError> {
+        if outcome_index >= self.config.num_outcomes {
+            return Err(MarketError::InvalidOutcomeIndex(outcome_index));
+        }
+        if !(target_prob > 0.0 && target_prob < 1.0) {
+            return Err(MarketError::CalculationError(
+                format!("Target probability {} is unreachable", target_prob)
+            ));
+        }
+        self.config.validate()?;
+
+        let quantities = self.outcome_quantities(bets)?;
+        if quantities.len() < 2 {
+            return Err(MarketError::InsufficientData("Market needs at least two outcomes".to_string()));
+        }
+
+        let additional = match &self.config.adaptive_liquidity {
+            Some(_) => self.adaptive_volume_to_reach(&quantities, outcome_index, target_prob)?,
+            None => {
+                // Closed form over the scaled quantities q_i / b_i
+                let liquidity = match &self.config.outcome_liquidity {
+                    Some(outcome_liquidity) => outcome_liquidity.clone(),
+                    None => vec![self.config.liquidity_param; quantities.len()],
+                };
+                let scaled: Vec<f64> = quantities.iter().zip(&liquidity).map(|(q, b)| q / b).collect();
+                let required = lmsr::quantity_for_price(&scaled, 1.0, outcome_index, target_prob)
+                    .ok_or_else(|| MarketError::InsufficientData("Market needs at least two outcomes".to_string()))?;
+
+                required * liquidity[outcome_index] - quantities[outcome_index]
+            }
+        };
+
+        if additional < 0.0 {
+            return Err(MarketError::CalculationError(
+                format!("Reaching {} would require negative volume on outcome {}", target_prob, outcome_index)
+            ));
+        }
+
+        Ok(additional)
+    }
+
+    // Adaptive `b` grows with the very volume being added, so there is no
+    // closed form. The outcome's price still rises with its own volume, so
+    // bracket the target by doubling and then bisect. Because `b` keeps
+    // growing the price levels off below 1, and targets past that level
+    // are unreachable.
+    fn adaptive_volume_to_reach(&self, quantities: &[f64], outcome: usize, target: f64) -> Result<f64, MarketError> {
+        let price_after = |additional: f64| -> Result<f64, MarketError> {
+            let mut moved = quantities.to_vec();
+            moved[outcome] += additional;
+            let liquidity = self.config.effective_liquidity(moved.iter().sum());
+            if !liquidity.is_finite() || liquidity <= 0.0 {
+                return Err(MarketError::InvalidLiquidity(format!("Effective liquidity {} is not positive", liquidity)));
+            }
+            Ok(lmsr::prices(&moved, liquidity)[outcome])
+        };
+
+        if price_after(0.0)? > target {
+            return Err(MarketError::CalculationError(
+                format!("Reaching {} would require negative volume on outcome {}", target, outcome)
+            ));
+        }
+
+        let mut low = 0.0;
+        let mut high = self.config.liquidity_param;
+        while price_after(high)? < target {
+            low = high;
+            high *= 2.0;
+            if !high.is_finite() {
+                return Err(MarketError::CalculationError(
+                    format!("Target probability {} is unreachable under adaptive liquidity", target)
+                ));
+            }
+        }
+
+        for _ in 0..VOLUME_SEARCH_STEPS {
+            let mid = (low + high) / 2.0;
+            if price_after(mid)? < target {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(high)
+    }
+
+    // Replays `ordered_bets` against the maker's ask quotes. Each bet buys
+    // `amount` shares at the ask, earning the spread over the fair price plus
+    // the maker rebate. With a `winning_outcome` the short inventory is settled instead
+    // of being marked at the fair price at fill time.
+    pub fn backtest_pnl(&self, ordered_bets: &[Bet], winning_outcome: Option<usize>) -> Result<f64, MarketError> {
+        if let Some(outcome) = winning_outcome {
+            if outcome >= self.config.num_outcomes {
+                return Err(MarketError::InvalidOutcomeIndex(outcome));
+            }
+        }
+
+        let rebate_rate = Decimal::from(self.config.maker_rebate_bps) / Decimal::from(10_000);
+        let mut inventory = vec![Decimal::ZERO; self.config.num_outcomes];
+        let mut collected = Decimal::ZERO;
+        let mut spread_revenue = Decimal::ZERO;
+
+        for (filled, bet) in ordered_bets.iter().enumerate() {
+            let probabilities = self.calculate_market_probabilities(&ordered_bets[..filled])?;
+            if bet.option_id >= probabilities.len() {
+                return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
+            }
+
+            let shares = Decimal::from_f64(bet.amount)
+                .ok_or_else(|| MarketError::CalculationError(format!("Invalid bet amount {}", bet.amount)))?;
+            let fair = probabilities[bet.option_id];
+            let paid = shares * fair * (Decimal::ONE + BASE_HALF_SPREAD);
+
+            inventory[bet.option_id] += shares;
+            collected += paid * (Decimal::ONE + rebate_rate);
+            spread_revenue += paid - shares * fair + paid * rebate_rate;
+        }
+
+        let pnl = match winning_outcome {
+            Some(outcome) => collected - inventory[outcome],
+            None => spread_revenue,
+        };
+
+        pnl.to_f64()
+            .ok_or_else(|| MarketError::CalculationError("P&L out of range".to_string()))
+    }
+
+    // Signed directional pressure per outcome:
+    // (volume_on_outcome - avg_volume) / total_volume. Zeros for an empty market.
+    pub fn order_imbalance(&self, bets: &[Bet]) -> Result<Vec<f64>, MarketError> {
+        let volumes = self.outcome_quantities(bets)?;
+        let total_volume: f64 = volumes.iter().sum();
+        if total_volume <= 0.0 {
+            return Ok(vec![0.0; volumes.len()]);
+        }
+
+        let avg_volume = total_volume / volumes.len() as f64;
+
+        Ok(volumes.iter().map(|volume| (volume - avg_volume) / total_volume).collect())
+    }
+
+    // Smallest liquidity `b` for which spending `trade_size` on an outcome
+    // priced at `starting_probability` moves it by at most `max_impact`.
+    // Spending c under LMSR lifts p to 1 - (1 - p)·e^(-c/b), so
+    // b = -c / ln(1 - max_impact / (1 - p)).
+    pub fn liquidity_for_impact_cap(
+        outcome_count: usize,
+        trade_size: f64,
+        max_impact: f64,
+        starting_probability: f64
+    ) -> Result<f64, MarketError> {
+        if outcome_count < 2 {
+            return Err(MarketError::InsufficientData("Market needs at least two outcomes".to_string()));
+        }
+        if !trade_size.is_finite() || trade_size <= 0.0 {
+            return Err(MarketError::CalculationError(format!("Invalid trade size {}", trade_size)));
+        }
+        if !(starting_probability > 0.0 && starting_probability < 1.0) {
+            return Err(MarketError::CalculationError(
+                format!("Starting probability {} must lie strictly between 0 and 1", starting_probability)
+            ));
+        }
+        // The price can never rise past 1, so caps at or above the remaining
+        // headroom are met by any liquidity
+        let headroom = 1.0 - starting_probability;
+        if !(max_impact > 0.0 && max_impact < headroom) {
+            return Err(MarketError::CalculationError(
+                format!("Impact cap {} must lie strictly between 0 and {}", max_impact, headroom)
+            ));
+        }
+
+        Ok(-trade_size / (1.0 - max_impact / headroom).ln())
+    }
+
+    // Probability implied by the price a trader actually transacts at:
+    // the ask plus the taker fee for buys, the bid less the taker fee for sells
+    pub fn effective_probability(&self, bets: &[Bet], outcome_index: usize, side: Side) -> Result<f64, MarketError> {
+        let probabilities = self.calculate_market_probabilities(bets)?;
+        if outcome_index >= probabilities.len() {
+            return Err(MarketError::InvalidOutcomeIndex(outcome_index));
+        }
+
+        let fee_rate = Decimal::from(self.config.taker_fee_bps) / Decimal::from(10_000);
+        let effective = match side {
+            Side::Buy => {
+                let ask = self.calculate_ask_prices(&probabilities, BASE_HALF_SPREAD)[outcome_index];
+                ask * (Decimal::ONE + fee_rate)
+            }
+            Side::Sell => {
+                let bid = self.calculate_bid_prices(&probabilities, BASE_HALF_SPREAD)[outcome_index];
+                bid * (Decimal::ONE - fee_rate)
+            }
+        };
+
+        effective.to_f64()
+            .ok_or_else(|| MarketError::CalculationError("Effective probability out of range".to_string()))
+    }
+
+    // Offsetting trades (outcome, shares; negative sells) that move the
+    // maker's `inventory` toward equal holdings on every outcome, which pays
+    // the same whatever wins. Each trade is capped at the current effective
+    // liquidity so one hedge can't push the price too far; a flat inventory
+    // yields no trades.
+    pub fn hedge_recommendation(&self, bets: &[Bet], inventory: &[f64]) -> Result<Vec<(usize, f64)>, MarketError> {
+        if inventory.len() != self.config.num_outcomes {
+            return Err(MarketError::CalculationError(
+                format!("Inventory has {} outcomes, expected {}", inventory.len(), self.config.num_outcomes)
+            ));
+        }
+        if let Some(shares) = inventory.iter().find(|shares| !shares.is_finite()) {
+            return Err(MarketError::CalculationError(format!("Invalid inventory {}", shares)));
+        }
+
+        let volume: f64 = self.outcome_quantities(bets)?.iter().sum();
+        let liquidity = self.config.effective_liquidity(volume);
+        if !liquidity.is_finite() || liquidity <= 0.0 {
+            return Err(MarketError::InvalidLiquidity(liquidity.to_string()));
+        }
+
+        let target = inventory.iter().sum::<f64>() / inventory.len() as f64;
+
+        Ok(inventory.iter()
+            .enumerate()
+            .map(|(outcome, shares)| (outcome, (target - shares).clamp(-liquidity, liquidity)))
+            .filter(|(_, trade)| trade.abs() > f64::EPSILON * target.abs().max(1.0))
+            .collect())
+    }
+
+    pub(crate) fn strategy_from_probabilities(&self, probabilities: &Probabilities) -> MarketMakingStrategy {
+        self.strategy_with_half_spread(probabilities, BASE_HALF_SPREAD)
+    }
+
+    fn strategy_with_half_spread(&self, probabilities: &[Decimal], half_spread: Decimal) -> MarketMakingStrategy {
         // Compute bid and ask prices
-        let bid_prices = self.calculate_bid_prices(&probabilities);
-        let ask_prices = self.calculate_ask_prices(&probabilities);
+        let bid_prices = self.calculate_bid_prices(probabilities, half_spread);
+        let ask_prices = self.calculate_ask_prices(probabilities, half_spread);
 
-        // Calculate spread
-        let spread = self.calculate_spread(&bid_prices, &ask_prices);
+        // Calculate spread net of the maker rebate and taker fee
+        let spread = self.calculate_net_spread(&bid_prices, &ask_prices);
+
+        // A round trip captures the full spread, so each fill earns half
+        let net_edge = spread / Decimal::from(2);
 
         // Recommend liquidity based on market conditions
-        let recommended_liquidity = self.calculate_recommended_liquidity(&probabilities);
+        let recommended_liquidity = self.calculate_recommended_liquidity(probabilities);
 
-        Ok(MarketMakingStrategy {
+        MarketMakingStrategy {
             bid_prices: bid_prices.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect(),
             ask_prices: ask_prices.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect(),
             spread: spread.to_f64().unwrap_or(0.0),
             recommended_liquidity: recommended_liquidity.to_f64().unwrap_or(0.0),
-        })
+            net_edge: net_edge.to_f64().unwrap_or(0.0),
+        }
     }
 
-    fn calculate_market_probabilities(&self, bets: &[Bet]) -> Result<Vec<Decimal>, MarketError> {
-        let liquidity_param = Decimal::from_f64(self.config.liquidity_param)
-            .ok_or_else(|| MarketError::InvalidLiquidity("Invalid liquidity parameter".to_string()))?;
-        let initial_liquidity = liquidity_param / Decimal::from(self.config.num_outcomes);
+    pub(crate) fn calculate_market_probabilities(&self, bets: &[Bet]) -> Result<Probabilities, MarketError> {
+        self.probability_engine.calculate_probabilities(bets)
+    }
 
-        let mut outcome_totals = vec![initial_liquidity; self.config.num_outcomes];
+    fn outcome_quantities(&self, bets: &[Bet]) -> Result<Vec<f64>, MarketError> {
+        let mut quantities = vec![0.0; self.config.num_outcomes];
         for bet in bets {
             if bet.option_id >= self.config.num_outcomes {
                 return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
             }
-            let bet_amount = Decimal::from_f64(bet.amount).unwrap_or(Decimal::ZERO);
-            outcome_totals[bet.option_id] += bet_amount;
+            quantities[bet.option_id] += bet.amount;
         }
 
-        let total_volume: Decimal = outcome_totals.iter().sum();
-
-        outcome_totals.iter()
-            .map(|&amount| Ok(amount / total_volume))
-            .collect()
+        Ok(quantities)
     }
 
-    fn calculate_bid_prices(&self, probabilities: &[Decimal]) -> Vec<Decimal> {
+    fn calculate_bid_prices(&self, probabilities: &[Decimal], half_spread: Decimal) -> Vec<Decimal> {
         probabilities.iter()
-            .map(|&prob| prob * Decimal::new(95, 2)) // Slightly lower than market price
+            .map(|&prob| prob * (Decimal::ONE - half_spread)) // Slightly lower than market price
             .collect()
     }
 
-    fn calculate_ask_prices(&self, probabilities: &[Decimal]) -> Vec<Decimal> {
+    fn calculate_ask_prices(&self, probabilities: &[Decimal], half_spread: Decimal) -> Vec<Decimal> {
         probabilities.iter()
-            .map(|&prob| prob * Decimal::new(105, 2)) // Slightly higher than market price
+            .map(|&prob| prob * (Decimal::ONE + half_spread)) // Slightly higher than market price
             .collect()
     }
 
-    fn calculate_spread(&self, bid_prices: &[Decimal], ask_prices: &[Decimal]) -> Decimal {
+    fn calculate_net_spread(&self, bid_prices: &[Decimal], ask_prices: &[Decimal]) -> Decimal {
+        // Makers earn the rebate on the notional of both legs, and pay the
+        // taker fee on the same notional when they flatten the inventory
+        // each fill leaves them with
+        let rebate_rate = Decimal::from(self.config.maker_rebate_bps) / Decimal::from(10_000);
+        let fee_rate = Decimal::from(self.config.taker_fee_bps) / Decimal::from(10_000);
+
         bid_prices.iter()
             .zip(ask_prices.iter())
-            .map(|(bid, ask)| ask - bid)
+            .map(|(bid, ask)| (ask - bid) + (ask + bid) * (rebate_rate - fee_rate))
             .sum::<Decimal>() / Decimal::from(bid_prices.len())
     }
 
     fn calculate_recommended_liquidity(&self, probabilities: &[Decimal]) -> Decimal {
         // More concentrated probabilities suggest lower liquidity recommendation
         let entropy = -probabilities.iter()
-            .map(|&p| if p > Decimal::ZERO { p * p.ln() } else { Decimal::ZERO })
+            .map(|&p| if p > Decimal::ZERO { p * p.max(PROBABILITY_EPSILON).ln() } else { Decimal::ZERO })
             .sum::<Decimal>();
 
         // Higher entropy (more uncertainty) leads to higher liquidity recommendation
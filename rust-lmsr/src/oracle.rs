@@ -0,0 +1,40 @@
+use crate::SettlementOutcome;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OracleError {
+    #[error("Market {0} has not been resolved")]
+    Unresolved(String),
+    #[error("Oracle unavailable: {0}")]
+    Unavailable(String),
+}
+
+// Source of truth for a market's winning outcome
+pub trait OutcomeOracle {
+    fn resolve(&self, market_id: &str) -> Result<SettlementOutcome, OracleError>;
+}
+
+// Outcomes set by hand, e.g. by an admin after checking the event
+#[derive(Debug, Clone, Default)]
+pub struct ManualOracle {
+    outcomes: HashMap<String, SettlementOutcome>,
+}
+
+impl ManualOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_outcome(&mut self, market_id: &str, outcome: SettlementOutcome) {
+        self.outcomes.insert(market_id.to_string(), outcome);
+    }
+}
+
+impl OutcomeOracle for ManualOracle {
+    fn resolve(&self, market_id: &str) -> Result<SettlementOutcome, OracleError> {
+        self.outcomes.get(market_id)
+            .cloned()
+            .ok_or_else(|| OracleError::Unresolved(market_id.to_string()))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{MarketError, Side};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LimitOrder {
+    pub outcome: usize,
+    pub side: Side,
+    pub target_probability: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FilledOrder {
+    pub order_id: u64,
+    pub order: LimitOrder,
+    pub fill_probability: f64,
+}
+
+// Resting orders matched off-chain against the current LMSR prices
+#[derive(Debug, Default)]
+pub struct LimitOrderBook {
+    orders: Vec<(u64, LimitOrder)>,
+    next_id: u64,
+}
+
+impl LimitOrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn place(&mut self, order: LimitOrder) -> Result<u64, MarketError> {
+        if !(order.target_probability > 0.0 && order.target_probability < 1.0) {
+            return Err(MarketError::CalculationError(
+                format!("Target probability {} must be between 0 and 1", order.target_probability)
+            ));
+        }
+        if !order.size.is_finite() || order.size <= 0.0 {
+            return Err(MarketError::CalculationError(
+                format!("Order size {} must be positive", order.size)
+            ));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.orders.push((id, order));
+
+        Ok(id)
+    }
+
+    pub fn cancel(&mut self, order_id: u64) -> Option<LimitOrder> {
+        let position = self.orders.iter().position(|(id, _)| *id == order_id)?;
+        Some(self.orders.remove(position).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    // Buys fill at or below their target, sells at or above it
+    pub fn match_against(&self, current_probs: &[f64]) -> Vec<FilledOrder> {
+        self.orders.iter()
+            .filter_map(|(id, order)| {
+                let probability = *current_probs.get(order.outcome)?;
+                let fills = match order.side {
+                    Side::Buy => probability <= order.target_probability,
+                    Side::Sell => probability >= order.target_probability,
+                };
+
+                fills.then(|| FilledOrder {
+                    order_id: *id,
+                    order: order.clone(),
+                    fill_probability: probability,
+                })
+            })
+            .collect()
+    }
+}
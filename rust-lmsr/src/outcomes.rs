@@ -0,0 +1,119 @@
+use crate::{Bet, MarketError, PROBABILITY_EPSILON};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use std::ops::Deref;
+
+// Volume per outcome including the liquidity seed (see `outcome_totals`).
+// Non-empty and strictly positive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeTotals(Vec<Decimal>);
+
+impl OutcomeTotals {
+    pub fn new(totals: Vec<Decimal>) -> Result<Self, MarketError> {
+        if totals.is_empty() {
+            return Err(MarketError::InsufficientData("Market has no outcomes".to_string()));
+        }
+        if let Some((outcome, total)) = totals.iter().enumerate().find(|(_, total)| **total <= Decimal::ZERO) {
+            return Err(MarketError::CalculationError(
+                format!("Outcome {} has non-positive total {}", outcome, total)
+            ));
+        }
+
+        Ok(OutcomeTotals(totals))
+    }
+
+    // Adds `bet` to its outcome, rejecting it if the total would not stay positive
+    pub fn add_bet(&mut self, bet: &Bet) -> Result<(), MarketError> {
+        let total = self.0.get_mut(bet.option_id)
+            .ok_or(MarketError::InvalidOutcomeIndex(bet.option_id))?;
+        let updated = *total + Decimal::from_f64(bet.amount).unwrap_or(Decimal::ZERO);
+        if updated <= Decimal::ZERO {
+            return Err(MarketError::CalculationError(
+                format!("Bet of {} leaves outcome {} with non-positive total", bet.amount, bet.option_id)
+            ));
+        }
+
+        *total = updated;
+        Ok(())
+    }
+
+    pub fn volume(&self) -> Decimal {
+        self.0.iter().sum()
+    }
+
+    pub fn into_inner(self) -> Vec<Decimal> {
+        self.0
+    }
+}
+
+impl Deref for OutcomeTotals {
+    type Target = [Decimal];
+
+    fn deref(&self) -> &[Decimal] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a OutcomeTotals {
+    type Item = &'a Decimal;
+    type IntoIter = std::slice::Iter<'a, Decimal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// A distribution over outcomes: non-negative and summing to 1 within
+// `PROBABILITY_EPSILON`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Probabilities(Vec<Decimal>);
+
+impl Probabilities {
+    pub fn new(probabilities: Vec<Decimal>) -> Result<Self, MarketError> {
+        if probabilities.is_empty() {
+            return Err(MarketError::InsufficientData("Market has no outcomes".to_string()));
+        }
+        if let Some(p) = probabilities.iter().find(|p| **p < Decimal::ZERO) {
+            return Err(MarketError::CalculationError(format!("Negative probability {}", p)));
+        }
+        let sum: Decimal = probabilities.iter().sum();
+        if (sum - Decimal::ONE).abs() > PROBABILITY_EPSILON {
+            return Err(MarketError::CalculationError(
+                format!("Probabilities sum to {}, expected 1", sum)
+            ));
+        }
+
+        Ok(Probabilities(probabilities))
+    }
+
+    // Volume share of each outcome
+    pub fn proportional(totals: &OutcomeTotals) -> Result<Self, MarketError> {
+        let volume = totals.volume();
+        Self::new(totals.iter().map(|total| total / volume).collect())
+    }
+
+    pub fn to_f64_vec(&self) -> Vec<f64> {
+        self.0.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect()
+    }
+
+    pub fn into_inner(self) -> Vec<Decimal> {
+        self.0
+    }
+}
+
+impl Deref for Probabilities {
+    type Target = [Decimal];
+
+    fn deref(&self) -> &[Decimal] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a Probabilities {
+    type Item = &'a Decimal;
+    type IntoIter = std::slice::Iter<'a, Decimal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
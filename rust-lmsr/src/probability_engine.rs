@@ -1,70 +1,202 @@
-use crate::{Bet, MarketConfig, MarketError, MarketType};
+use crate::{lmsr, outcome_totals, Bet, MarketConfig, MarketError, MarketType, OutcomeTotals, Probabilities, PROBABILITY_EPSILON};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
+// Drift from 1 tolerated before probabilities are rejected (1e-12)
+const DEFAULT_SUM_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 12);
+
+// Slack allowed when known probabilities are checked against 1
+const BOUNDS_TOLERANCE: f64 = 1e-9;
+
 pub struct ProbabilityEngine {
     config: MarketConfig,
+    sum_tolerance: Decimal,
 }
 
 impl ProbabilityEngine {
     pub fn new(config: MarketConfig) -> Self {
-        ProbabilityEngine { config }
+        ProbabilityEngine { config, sum_tolerance: DEFAULT_SUM_TOLERANCE }
+    }
+
+    pub fn with_sum_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.sum_tolerance = tolerance;
+        self
+    }
+
+    pub fn calculate_probabilities(&self, bets: &[Bet]) -> Result<Probabilities, MarketError> {
+        self.probabilities_from_totals(&outcome_totals(&self.config, bets)?)
     }
 
-    pub fn calculate_probabilities(&self, bets: &[Bet]) -> Result<Vec<Decimal>, MarketError> {
-        // Validate input
-        if bets.iter().any(|b| b.option_id >= self.config.num_outcomes) {
-            return Err(MarketError::InvalidOutcomeIndex(
-                bets.iter()
-                    .find(|b| b.option_id >= self.config.num_outcomes)
-                    .map(|b| b.option_id)
-                    .unwrap_or(0)
+    // Probabilities for already-aggregated outcome totals (see `outcome_totals`)
+    pub(crate) fn probabilities_from_totals(&self, totals: &OutcomeTotals) -> Result<Probabilities, MarketError> {
+        let probabilities = self.unchecked_from_totals(totals)?;
+
+        // Invariant: probabilities sum to 1 up to rounding
+        let sum: Decimal = probabilities.iter().sum();
+        let drift = (sum - Decimal::ONE).abs();
+        debug_assert!(drift <= PROBABILITY_EPSILON, "probabilities sum to {}", sum);
+        if drift > self.sum_tolerance {
+            return Err(MarketError::CalculationError(
+                format!("Probabilities sum to {}, expected 1", sum)
             ));
         }
 
-        // Liquidity calculation
-        let liquidity = Decimal::from_f64(self.config.liquidity_param)
-            .ok_or_else(|| MarketError::InvalidLiquidity("Invalid liquidity parameter".to_string()))?;
-        let initial_liquidity = liquidity / Decimal::from(self.config.num_outcomes);
+        Probabilities::new(probabilities)
+    }
+
+    // Invariant check: adding `bet` must strictly raise its outcome's
+    // probability and must not raise any other outcome's
+    pub fn verify_monotonicity(&self, bets: &[Bet], bet: Bet) -> Result<bool, MarketError> {
+        let outcome = bet.option_id;
+        let before = self.calculate_probabilities(bets)?;
+
+        let mut extended = bets.to_vec();
+        extended.push(bet);
+        let after = self.calculate_probabilities(&extended)?;
+
+        let others_hold = before.iter()
+            .zip(&after)
+            .enumerate()
+            .filter(|&(i, _)| i != outcome)
+            .all(|(_, (before, after))| after <= before);
+
+        Ok(after[outcome] > before[outcome] && others_hold)
+    }
+
+    // Sum of the computed probabilities, without enforcing the invariant
+    pub fn probabilities_sum(&self, bets: &[Bet]) -> Result<Decimal, MarketError> {
+        let totals = outcome_totals(&self.config, bets)?;
+        Ok(self.unchecked_from_totals(&totals)?.iter().sum())
+    }
 
-        // Aggregate outcome totals
-        let mut outcome_totals = vec![initial_liquidity; self.config.num_outcomes];
-        for bet in bets {
-            let bet_amount = Decimal::from_f64(bet.amount).unwrap_or(Decimal::ZERO);
-            outcome_totals[bet.option_id] += bet_amount;
+    // LMSR prices p_i ∝ exp(q_i / b_i), see `scaled_quantities`
+    fn unchecked_from_totals(&self, totals: &OutcomeTotals) -> Result<Vec<Decimal>, MarketError> {
+        let scaled = self.scaled_quantities(totals)?;
+
+        // Normalized in Decimal so the sum invariant holds exactly
+        let prices = lmsr::prices(&scaled, 1.0).into_iter()
+            .map(|p| Decimal::from_f64(p).ok_or_else(|| MarketError::CalculationError(format!("Invalid price {}", p))))
+            .collect::<Result<Vec<Decimal>, MarketError>>()?;
+        let sum: Decimal = prices.iter().sum();
+        if sum <= Decimal::ZERO {
+            return Err(MarketError::CalculationError("Division by zero in probability calculation".to_string()));
         }
 
-        // Exponential scaling
-        let max_total = outcome_totals.iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .cloned()
-            .unwrap_or(Decimal::ZERO);
+        Ok(prices.iter().map(|p| p / sum).collect())
+    }
+
+    // Partition function Z = Σ exp(q_i / b_i), with the same quantities and
+    // liquidity as the probabilities, so p_i = exp(q_i / b_i) / Z. Summed
+    // via log-sum-exp; very large markets overflow to infinity.
+    pub fn partition_function(&self, bets: &[Bet]) -> Result<f64, MarketError> {
+        let scaled = self.scaled_quantities(&outcome_totals(&self.config, bets)?)?;
+        Ok(lmsr::cost(&scaled, 1.0).exp())
+    }
 
-        let scale_factor = max_total / Decimal::new(10, 0);
+    // q_i / b_i over the traded quantities (totals less the liquidity seed).
+    // `b_i` is the per-outcome liquidity when configured, otherwise the
+    // scalar `b`, grown by volume if adaptive.
+    pub(crate) fn scaled_quantities(&self, totals: &OutcomeTotals) -> Result<Vec<f64>, MarketError> {
+        self.config.validate()?;
 
-        // Compute exponentials with scaling
-        let exp_values: Vec<Decimal> = outcome_totals.iter()
-            .map(|&total| {
-                let scaled_total = total / scale_factor;
-                // exp() returns Decimal directly, not Result
-                scaled_total.exp()
-            })
+        let seeds = outcome_totals(&self.config, &[])?;
+        let quantities: Vec<f64> = totals.iter()
+            .zip(seeds.iter())
+            .map(|(total, seed)| (total - seed).to_f64().unwrap_or(0.0))
             .collect();
 
-        // Sum of exponentials
-        let sum_exp = exp_values.iter()
-            .try_fold(Decimal::ZERO, |acc, &x| acc.checked_add(x))
-            .ok_or_else(|| MarketError::CalculationError("Numerical overflow in sum".to_string()))?;
-
-        // Final probabilities
-        let probabilities = exp_values.iter()
-            .map(|&exp_val| {
-                exp_val.checked_div(sum_exp)
-                    .ok_or_else(|| MarketError::CalculationError("Division by zero in probability calculation".to_string()))
-            })
-            .collect::<Result<Vec<Decimal>, MarketError>>()?;
+        let scaled = match &self.config.outcome_liquidity {
+            Some(outcome_liquidity) => quantities.iter()
+                .zip(outcome_liquidity)
+                .map(|(q, b)| q / b)
+                .collect(),
+            None => {
+                let liquidity = self.config.effective_liquidity(quantities.iter().sum());
+                if !liquidity.is_finite() || liquidity <= 0.0 {
+                    return Err(MarketError::InvalidLiquidity(format!("Effective liquidity {} is not positive", liquidity)));
+                }
+                quantities.iter().map(|q| q / liquidity).collect()
+            }
+        };
+
+        Ok(scaled)
+    }
+
+    // Feasible (min, max) probability for each outcome not in `known`, in
+    // index order, given all probabilities are non-negative and sum to 1
+    pub fn arbitrage_free_bounds(known: &[(usize, f64)], num_outcomes: usize) -> Result<Vec<(f64, f64)>, MarketError> {
+        let mut is_known = vec![false; num_outcomes];
+        let mut known_total = 0.0;
+        for &(index, probability) in known {
+            if index >= num_outcomes || is_known[index] {
+                return Err(MarketError::InvalidOutcomeIndex(index));
+            }
+            if !probability.is_finite() || !(0.0..=1.0).contains(&probability) {
+                return Err(MarketError::CalculationError(
+                    format!("Invalid probability {} for outcome {}", probability, index)
+                ));
+            }
+            is_known[index] = true;
+            known_total += probability;
+        }
+
+        let remaining = 1.0 - known_total;
+        let unknown = is_known.iter().filter(|&&k| !k).count();
+        let inconsistent = remaining < -BOUNDS_TOLERANCE
+            || (unknown == 0 && remaining.abs() > BOUNDS_TOLERANCE);
+        if inconsistent {
+            return Err(MarketError::CalculationError(
+                format!("Known probabilities sum to {}, leaving no feasible remainder", known_total)
+            ));
+        }
+
+        let remaining = remaining.max(0.0);
+        // A lone unknown outcome is pinned to whatever mass is left
+        let min = if unknown == 1 { remaining } else { 0.0 };
+
+        Ok(vec![(min, remaining); unknown])
+    }
+
+    // Win probability at which buying at `entry_price` (paying 1 on a win)
+    // has zero expected value once a `fee_bps` fee on the stake is included:
+    // q·1 = entry_price·(1 + fee)
+    pub fn break_even_probability(&self, entry_price: f64, fee_bps: u32) -> Result<f64, MarketError> {
+        if !(entry_price > 0.0 && entry_price < 1.0) {
+            return Err(MarketError::CalculationError(
+                format!("Entry price {} must lie strictly between 0 and 1", entry_price)
+            ));
+        }
+
+        let break_even = entry_price * (1.0 + fee_bps as f64 / 10_000.0);
+        if break_even > 1.0 {
+            return Err(MarketError::CalculationError(
+                format!("Fees of {} bps leave no break-even probability at price {}", fee_bps, entry_price)
+            ));
+        }
+
+        Ok(break_even)
+    }
+
+    // Sums runs of adjacent fine buckets into `target_buckets` coarse ones,
+    // keeping total mass unchanged
+    pub fn rebucket(&self, probs: &[f64], target_buckets: usize) -> Result<Vec<f64>, MarketError> {
+        if target_buckets == 0 || target_buckets > probs.len() {
+            return Err(MarketError::CalculationError(
+                format!("Cannot rebucket {} buckets into {}", probs.len(), target_buckets)
+            ));
+        }
+        if !probs.len().is_multiple_of(target_buckets) {
+            return Err(MarketError::CalculationError(
+                format!("{} buckets do not divide evenly into {}", probs.len(), target_buckets)
+            ));
+        }
+        if let Some(p) = probs.iter().find(|p| !p.is_finite() || **p < 0.0) {
+            return Err(MarketError::CalculationError(format!("Invalid bucket probability {}", p)));
+        }
+
+        let width = probs.len() / target_buckets;
 
-        Ok(probabilities)
+        Ok(probs.chunks(width).map(|chunk| chunk.iter().sum()).collect())
     }
 
     pub fn calculate_price(&self, bets: &[Bet], outcome_index: usize) -> Result<Decimal, MarketError> {
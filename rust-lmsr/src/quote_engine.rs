@@ -0,0 +1,91 @@
+use crate::{Bet, MarketConfig, MarketError, MarketMakerEngine, MarketMakingStrategy};
+use std::time::Duration;
+
+// Quotes older than this are refreshed even if nothing moved
+const DEFAULT_MAX_QUOTE_AGE: Duration = Duration::from_secs(60);
+
+// Mid move, in probability, that makes a standing quote stale
+const DEFAULT_REFRESH_THRESHOLD: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteRefresh {
+    pub strategy: MarketMakingStrategy,
+    // Whether `strategy` replaced the standing quotes
+    pub refreshed: bool,
+}
+
+// Keeps a maker's standing quotes and decides when to replace them. Quotes
+// are re-centered for inventory and widened for volatility, and only
+// re-published once a mid has drifted past the refresh threshold or the
+// quotes have aged past `max_quote_age`.
+pub struct QuoteEngine {
+    maker: MarketMakerEngine,
+    max_quote_age: Duration,
+    refresh_threshold: f64,
+    standing: Option<MarketMakingStrategy>,
+}
+
+impl QuoteEngine {
+    pub fn new(config: MarketConfig) -> Self {
+        QuoteEngine {
+            maker: MarketMakerEngine::new(config),
+            max_quote_age: DEFAULT_MAX_QUOTE_AGE,
+            refresh_threshold: DEFAULT_REFRESH_THRESHOLD,
+            standing: None,
+        }
+    }
+
+    pub fn with_max_quote_age(mut self, max_quote_age: Duration) -> Self {
+        self.max_quote_age = max_quote_age;
+        self
+    }
+
+    pub fn with_refresh_threshold(mut self, refresh_threshold: f64) -> Self {
+        self.refresh_threshold = refresh_threshold;
+        self
+    }
+
+    // Reprices for the current market, `inventory` and `realized_vol`, where
+    // `elapsed` is the time since the standing quotes were published. The
+    // standing quotes are kept unless they are missing, too old or stale.
+    pub fn refresh(
+        &mut self,
+        bets: &[Bet],
+        inventory: &[f64],
+        elapsed: Duration,
+        realized_vol: f64,
+    ) -> Result<QuoteRefresh, MarketError> {
+        let candidate = self.maker.simulate_strategy_with_inventory(bets, inventory, realized_vol)?;
+
+        let refreshed = match &self.standing {
+            Some(standing) => elapsed >= self.max_quote_age || self.mid_drift(standing, &candidate) > self.refresh_threshold,
+            None => true,
+        };
+        if refreshed {
+            self.standing = Some(candidate);
+        }
+
+        let strategy = self.standing.clone().expect("standing quotes were just set");
+        Ok(QuoteRefresh { strategy, refreshed })
+    }
+
+    // Standing quotes, if any have been published
+    pub fn standing(&self) -> Option<&MarketMakingStrategy> {
+        self.standing.as_ref()
+    }
+
+    // Largest move of any outcome's mid between two sets of quotes
+    fn mid_drift(&self, from: &MarketMakingStrategy, to: &MarketMakingStrategy) -> f64 {
+        let mids = |strategy: &MarketMakingStrategy| -> Vec<f64> {
+            strategy.bid_prices().iter()
+                .zip(strategy.ask_prices())
+                .map(|(bid, ask)| (bid + ask) / 2.0)
+                .collect()
+        };
+
+        mids(from).iter()
+            .zip(mids(to))
+            .map(|(before, after)| (after - before).abs())
+            .fold(0.0, f64::max)
+    }
+}
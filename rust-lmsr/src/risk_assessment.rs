@@ -1,72 +1,221 @@
-use crate::{Bet, MarketConfig, MarketError, MarketRiskProfile};
+use crate::{
+    kl_divergence, max_with_index, outcome_totals, Bet, BetVelocity, MarketConfig, MarketError, MarketRiskProfile,
+    Probabilities, ProbabilityEngine, TimedBet, PROBABILITY_EPSILON, PROBABILITY_EPSILON_F64,
+};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
+use std::time::Duration;
 
 pub struct RiskAssessmentEngine {
     config: MarketConfig,
+    // Risk is measured on the same LMSR prices the probability engine reports
+    probability_engine: ProbabilityEngine,
 }
 
 impl RiskAssessmentEngine {
     pub fn new(config: MarketConfig) -> Self {
-        RiskAssessmentEngine { config }
+        let probability_engine = ProbabilityEngine::new(config.clone());
+        RiskAssessmentEngine { config, probability_engine }
     }
 
     pub fn assess_risk(&self, bets: &[Bet]) -> Result<MarketRiskProfile, MarketError> {
         // Probability calculation
         let probabilities = self.calculate_market_probabilities(bets)?;
 
+        Ok(self.profile_from_probabilities(&probabilities, bets))
+    }
+
+    // `assess_risk` for timestamped bets, adding their velocity over `window`
+    pub fn assess_risk_timed(&self, ordered_bets: &[TimedBet], window: Duration) -> Result<MarketRiskProfile, MarketError> {
+        let velocity = self.bet_velocity(ordered_bets, window)?;
+        let bets: Vec<Bet> = ordered_bets.iter().map(|timed| timed.bet.clone()).collect();
+
+        let mut profile = self.assess_risk(&bets)?;
+        profile.velocity = Some(velocity);
+        Ok(profile)
+    }
+
+    // Realized volatility of the market's path: replays `ordered_bets` and
+    // returns the standard deviation of the per-bet LMSR probability changes
+    // for the outcome that moved the most in total.
+    pub fn path_volatility(&self, ordered_bets: &[Bet]) -> Result<f64, MarketError> {
+        if ordered_bets.len() < 2 {
+            return Err(MarketError::InsufficientData("Path volatility needs at least two bets".to_string()));
+        }
+
+        let mut outcome_totals = outcome_totals(&self.config, &[])?;
+        let mut previous = self.probability_engine.probabilities_from_totals(&outcome_totals)?.to_f64_vec();
+
+        let mut changes = vec![Vec::with_capacity(ordered_bets.len()); self.config.num_outcomes];
+        for bet in ordered_bets {
+            outcome_totals.add_bet(bet)?;
+
+            let current = self.probability_engine.probabilities_from_totals(&outcome_totals)?.to_f64_vec();
+            for (outcome, (now, before)) in current.iter().zip(&previous).enumerate() {
+                changes[outcome].push(now - before);
+            }
+            previous = current;
+        }
+
+        let total_movement = |deltas: &Vec<f64>| deltas.iter().map(|d| d.abs()).sum::<f64>();
+        let most_affected = changes.iter()
+            .max_by(|a, b| total_movement(a).total_cmp(&total_movement(b)))
+            .ok_or_else(|| MarketError::InsufficientData("Market has no outcomes".to_string()))?;
+
+        let n = most_affected.len() as f64;
+        let mean = most_affected.iter().sum::<f64>() / n;
+        let variance = most_affected.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / n;
+        Ok(variance.sqrt())
+    }
+
+    // How far `new_bet` moved the market: KL(after || before) in nats
+    pub fn information_gain(&self, before: &[Bet], new_bet: Bet) -> Result<f64, MarketError> {
+        let prior = self.probability_engine.calculate_probabilities(before)?.to_f64_vec();
+
+        let mut after = before.to_vec();
+        after.push(new_bet);
+        let posterior = self.probability_engine.calculate_probabilities(&after)?.to_f64_vec();
+
+        kl_divergence(&posterior, &prior)
+    }
+
+    // Rate of volume accumulation over the `window` ending at the latest bet,
+    // and its change from the window before. A spike in acceleration can
+    // signal activity ahead of an imminent resolution.
+    pub fn bet_velocity(&self, ordered_bets: &[TimedBet], window: Duration) -> Result<BetVelocity, MarketError> {
+        let last = ordered_bets.last()
+            .ok_or_else(|| MarketError::InsufficientData("Bet velocity needs at least one bet".to_string()))?;
+        let window = chrono::Duration::from_std(window)
+            .ok()
+            .filter(|window| *window > chrono::Duration::zero())
+            .ok_or_else(|| MarketError::CalculationError("Velocity window must be positive".to_string()))?;
+
+        // Volume in the window before the latest one, then the latest
+        let mut volumes = [0.0; 2];
+        for (i, timed) in ordered_bets.iter().enumerate() {
+            if i > 0 && timed.placed_at < ordered_bets[i - 1].placed_at {
+                return Err(MarketError::CalculationError("Bets must be in time order".to_string()));
+            }
+            if timed.bet.option_id >= self.config.num_outcomes {
+                return Err(MarketError::InvalidOutcomeIndex(timed.bet.option_id));
+            }
+            if !timed.bet.amount.is_finite() || timed.bet.amount < 0.0 {
+                return Err(MarketError::CalculationError(format!("Invalid bet amount {}", timed.bet.amount)));
+            }
+            let age = last.placed_at - timed.placed_at;
+            if age < window {
+                volumes[1] += timed.bet.amount;
+            } else if age < window * 2 {
+                volumes[0] += timed.bet.amount;
+            }
+        }
+
+        let seconds = window.num_milliseconds() as f64 / 1000.0;
+        let previous_rate = volumes[0] / seconds;
+        let rate = volumes[1] / seconds;
+
+        Ok(BetVelocity { rate, acceleration: (rate - previous_rate) / seconds })
+    }
+
+    // Value-at-Risk of a portfolio of independent positions. Each position is
+    // a market with its bets and the holder's profit if each outcome wins;
+    // the market's LMSR probabilities weight those outcomes. Returns
+    // the smallest loss not exceeded with probability `confidence`. Each
+    // position carries its own market config, so this engine's config does
+    // not enter the result.
+    //
+    // The joint loss distribution is kept as a histogram of VAR_BUCKETS
+    // buckets over the range of possible losses, so the work grows linearly
+    // with positions and outcomes. Each bucket carries its mean loss; the
+    // result is exact while distinct losses fall in distinct buckets and
+    // otherwise within one bucket width of the true quantile.
+    pub fn portfolio_var(&self, positions: &[(MarketConfig, Vec<Bet>, Vec<f64>)], confidence: f64) -> Result<f64, MarketError> {
+        if positions.is_empty() {
+            return Err(MarketError::InsufficientData("Portfolio VaR needs at least one position".to_string()));
+        }
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(MarketError::CalculationError(
+                format!("Confidence {} must lie strictly between 0 and 1", confidence)
+            ));
+        }
+
+        let mut histogram = LossHistogram::certain(0.0);
+        for (config, bets, profits) in positions {
+            config.validate()?;
+            if profits.len() != config.num_outcomes {
+                return Err(MarketError::CalculationError(
+                    format!("Position has {} outcome profits, expected {}", profits.len(), config.num_outcomes)
+                ));
+            }
+            if let Some(profit) = profits.iter().find(|profit| !profit.is_finite()) {
+                return Err(MarketError::CalculationError(format!("Invalid position profit {}", profit)));
+            }
+
+            let probabilities = ProbabilityEngine::new(config.clone()).calculate_probabilities(bets)?.to_f64_vec();
+            let losses: Vec<(f64, f64)> = profits.iter().map(|profit| -profit).zip(probabilities).collect();
+            histogram = histogram.add_independent(&losses);
+        }
+
+        Ok(histogram.quantile(confidence - PROBABILITY_EPSILON_F64))
+    }
+
+    // Assesses many markets at once. Each slot carries its own result so a
+    // single bad market doesn't abort the rest of the batch. With the
+    // `parallel` feature the markets are evaluated on the rayon pool.
+    pub fn assess_risk_batch(configs_and_bets: Vec<(MarketConfig, Vec<Bet>)>) -> Vec<Result<MarketRiskProfile, MarketError>> {
+        let assess = |(config, bets): (MarketConfig, Vec<Bet>)| {
+            config.validate()?;
+            RiskAssessmentEngine::new(config).assess_risk(&bets)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            configs_and_bets.into_par_iter().map(assess).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            configs_and_bets.into_iter().map(assess).collect()
+        }
+    }
+
+    pub(crate) fn profile_from_probabilities(&self, probabilities: &Probabilities, bets: &[Bet]) -> MarketRiskProfile {
         // Entropy calculation
-        let entropy = self.calculate_entropy(&probabilities);
+        let entropy = self.calculate_entropy(probabilities);
 
         // Market concentration
-        let concentration = self.calculate_concentration(&probabilities);
+        let concentration = self.calculate_concentration(probabilities);
 
         // Volatility estimation
-        let expected_volatility = self.estimate_volatility(&probabilities);
+        let expected_volatility = self.estimate_volatility(probabilities);
 
         // Liquidity risk assessment
         let liquidity_risk = self.assess_liquidity_risk(bets);
 
-        Ok(MarketRiskProfile {
-            probabilities: probabilities.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect(),
+        MarketRiskProfile {
+            probabilities: probabilities.to_f64_vec(),
             entropy: entropy.to_f64().unwrap_or(0.0),
             concentration: concentration.to_f64().unwrap_or(0.0),
             expected_volatility: expected_volatility.to_f64().unwrap_or(0.0),
             liquidity_risk: liquidity_risk.to_f64().unwrap_or(0.0),
-        })
-    }
-
-    fn calculate_market_probabilities(&self, bets: &[Bet]) -> Result<Vec<Decimal>, MarketError> {
-        let liquidity_param = Decimal::from_f64(self.config.liquidity_param)
-            .ok_or_else(|| MarketError::InvalidLiquidity("Invalid liquidity parameter".to_string()))?;
-        let initial_liquidity = liquidity_param / Decimal::from(self.config.num_outcomes);
-
-        let mut outcome_totals = vec![initial_liquidity; self.config.num_outcomes];
-        for bet in bets {
-            if bet.option_id >= self.config.num_outcomes {
-                return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
-            }
-            let bet_amount = Decimal::from_f64(bet.amount).unwrap_or(Decimal::ZERO);
-            outcome_totals[bet.option_id] += bet_amount;
+            velocity: None,
         }
+    }
 
-        let total_volume: Decimal = outcome_totals.iter().sum();
-
-        outcome_totals.iter()
-            .map(|&amount| Ok(amount / total_volume))
-            .collect()
+    fn calculate_market_probabilities(&self, bets: &[Bet]) -> Result<Probabilities, MarketError> {
+        self.probability_engine.calculate_probabilities(bets)
     }
 
     fn calculate_entropy(&self, probabilities: &[Decimal]) -> Decimal {
         -probabilities.iter()
-            .map(|&p| if p > Decimal::ZERO { p * p.ln() } else { Decimal::ZERO })
+            .map(|&p| if p > Decimal::ZERO { p * p.max(PROBABILITY_EPSILON).ln() } else { Decimal::ZERO })
             .sum::<Decimal>()
     }
 
     fn calculate_concentration(&self, probabilities: &[Decimal]) -> Decimal {
-        probabilities.iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .cloned()
+        max_with_index(probabilities)
+            .map(|(_, probability)| probability)
             .unwrap_or(Decimal::ZERO)
     }
 
@@ -97,4 +246,67 @@ impl RiskAssessmentEngine {
             Decimal::ONE // Maximum risk if no volume
         }
     }
-}
\ No newline at end of file
+}
+
+// Buckets in the portfolio loss histogram
+const VAR_BUCKETS: usize = 4096;
+
+// Loss distribution over `VAR_BUCKETS` equal buckets spanning [low, high],
+// each holding its probability mass and mass-weighted loss
+struct LossHistogram {
+    low: f64,
+    high: f64,
+    mass: Vec<f64>,
+    weighted_loss: Vec<f64>,
+}
+
+impl LossHistogram {
+    fn certain(loss: f64) -> Self {
+        let mut histogram = LossHistogram::empty(loss, loss);
+        histogram.insert(loss, 1.0);
+        histogram
+    }
+
+    fn empty(low: f64, high: f64) -> Self {
+        LossHistogram { low, high, mass: vec![0.0; VAR_BUCKETS], weighted_loss: vec![0.0; VAR_BUCKETS] }
+    }
+
+    fn insert(&mut self, loss: f64, p: f64) {
+        let width = (self.high - self.low) / VAR_BUCKETS as f64;
+        let bucket = if width > 0.0 { ((loss - self.low) / width) as usize } else { 0 };
+        let bucket = bucket.min(VAR_BUCKETS - 1);
+        self.mass[bucket] += p;
+        self.weighted_loss[bucket] += p * loss;
+    }
+
+    // Distribution of this loss plus an independent one given as (loss, probability)
+    fn add_independent(&self, losses: &[(f64, f64)]) -> Self {
+        let min = losses.iter().map(|&(loss, _)| loss).fold(f64::INFINITY, f64::min);
+        let max = losses.iter().map(|&(loss, _)| loss).fold(f64::NEG_INFINITY, f64::max);
+
+        let mut combined = LossHistogram::empty(self.low + min, self.high + max);
+        for (&mass, &weighted) in self.mass.iter().zip(&self.weighted_loss).filter(|(&mass, _)| mass > 0.0) {
+            let mean = weighted / mass;
+            for &(loss, p) in losses.iter().filter(|&&(_, p)| p > 0.0) {
+                combined.insert(mean + loss, mass * p);
+            }
+        }
+
+        combined
+    }
+
+    // Mean loss of the first bucket at which the cumulative mass reaches `level`
+    fn quantile(&self, level: f64) -> f64 {
+        let mut cumulative = 0.0;
+        let mut last = self.low;
+        for (&mass, &weighted) in self.mass.iter().zip(&self.weighted_loss).filter(|(&mass, _)| mass > 0.0) {
+            cumulative += mass;
+            last = weighted / mass;
+            if cumulative >= level {
+                break;
+            }
+        }
+
+        last
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{MarketError, PROBABILITY_EPSILON_F64};
+
+// Logarithmic score of a resolved market: -ln(p) of the outcome that won.
+// Lower is better; the floor keeps a confident miss finite (at most ~20.7).
+pub fn log_loss(predicted: &[f64], winning_outcome: usize) -> Result<f64, MarketError> {
+    let probability = predicted.get(winning_outcome)
+        .copied()
+        .ok_or(MarketError::InvalidOutcomeIndex(winning_outcome))?;
+
+    if !probability.is_finite() || probability < 0.0 {
+        return Err(MarketError::CalculationError(
+            format!("Invalid probability {} for outcome {}", probability, winning_outcome)
+        ));
+    }
+
+    Ok(-probability.max(PROBABILITY_EPSILON_F64).ln())
+}
+
+// Mean multi-category Brier score split per Murphy (1973):
+// brier = reliability - resolution + uncertainty
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrierDecomposition {
+    pub brier: f64,
+    pub reliability: f64,
+    pub resolution: f64,
+    pub uncertainty: f64,
+}
+
+// Decomposes the Brier score over (predicted distribution, realized outcome)
+// pairs. Observations are grouped by identical forecasts, which makes the
+// decomposition exact.
+pub fn brier_decomposition(observations: &[(Vec<f64>, usize)]) -> Result<BrierDecomposition, MarketError> {
+    let num_outcomes = observations.first()
+        .map(|(predicted, _)| predicted.len())
+        .ok_or_else(|| MarketError::InsufficientData("No resolved observations".to_string()))?;
+
+    // Distinct forecasts with their observation count and outcome tallies
+    let mut groups: Vec<(&[f64], f64, Vec<f64>)> = Vec::new();
+    let mut outcome_counts = vec![0.0; num_outcomes];
+    let mut brier_total = 0.0;
+
+    for (predicted, winning_outcome) in observations {
+        if predicted.len() != num_outcomes {
+            return Err(MarketError::CalculationError(
+                format!("Forecast has {} outcomes, expected {}", predicted.len(), num_outcomes)
+            ));
+        }
+        if *winning_outcome >= num_outcomes {
+            return Err(MarketError::InvalidOutcomeIndex(*winning_outcome));
+        }
+        if let Some(p) = predicted.iter().find(|p| !p.is_finite() || **p < 0.0) {
+            return Err(MarketError::CalculationError(format!("Invalid probability {}", p)));
+        }
+
+        brier_total += predicted.iter()
+            .enumerate()
+            .map(|(k, &p)| {
+                let observed = if k == *winning_outcome { 1.0 } else { 0.0 };
+                (p - observed).powi(2)
+            })
+            .sum::<f64>();
+        outcome_counts[*winning_outcome] += 1.0;
+
+        let index = match groups.iter().position(|(forecast, _, _)| *forecast == predicted.as_slice()) {
+            Some(index) => index,
+            None => {
+                groups.push((predicted.as_slice(), 0.0, vec![0.0; num_outcomes]));
+                groups.len() - 1
+            }
+        };
+        groups[index].1 += 1.0;
+        groups[index].2[*winning_outcome] += 1.0;
+    }
+
+    let total = observations.len() as f64;
+    let climatology: Vec<f64> = outcome_counts.iter().map(|count| count / total).collect();
+
+    let mut reliability = 0.0;
+    let mut resolution = 0.0;
+    for (forecast, count, tallies) in &groups {
+        for k in 0..num_outcomes {
+            let frequency = tallies[k] / count;
+            reliability += count * (forecast[k] - frequency).powi(2);
+            resolution += count * (frequency - climatology[k]).powi(2);
+        }
+    }
+
+    Ok(BrierDecomposition {
+        brier: brier_total / total,
+        reliability: reliability / total,
+        resolution: resolution / total,
+        uncertainty: climatology.iter().map(|o| o * (1.0 - o)).sum(),
+    })
+}
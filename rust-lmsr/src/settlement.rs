@@ -0,0 +1,251 @@
+use crate::{trade_costs, Bet, FeeLedger, FeeSplit, MarketConfig, MarketError, OutcomeOracle, PayoutModel};
+use serde::{Deserialize, Serialize};
+
+// Tolerance when checking that split weights sum to 1
+const WEIGHT_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SettlementOutcome {
+    // A single outcome won; each of its shares pays 1
+    Winner(usize),
+    // Event cancelled; every bet is refunded what it paid
+    Void,
+    // Shares of each listed outcome pay its weight; weights sum to 1
+    Split(Vec<(usize, f64)>),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PayoutStatus {
+    Pending,
+    Paid,
+}
+
+// Payout owed to one position (bet) and whether it has been made
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PositionPayout {
+    pub position: usize,
+    pub amount: f64,
+    pub status: PayoutStatus,
+}
+
+// Durable record of a settlement in progress. Persist it after every
+// payout so a crashed run can be resumed without paying anyone twice.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettlementLedger {
+    pub market_id: String,
+    pub outcome: SettlementOutcome,
+    pub payouts: Vec<PositionPayout>,
+}
+
+impl SettlementLedger {
+    pub fn is_complete(&self) -> bool {
+        self.payouts.iter().all(|payout| payout.status == PayoutStatus::Paid)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &PositionPayout> {
+        self.payouts.iter().filter(|payout| payout.status == PayoutStatus::Pending)
+    }
+}
+
+pub struct SettlementEngine {
+    config: MarketConfig,
+    oracle: Option<Box<dyn OutcomeOracle>>,
+}
+
+impl SettlementEngine {
+    pub fn new(config: MarketConfig) -> Self {
+        SettlementEngine { config, oracle: None }
+    }
+
+    pub fn with_oracle(mut self, oracle: Box<dyn OutcomeOracle>) -> Self {
+        self.oracle = Some(oracle);
+        self
+    }
+
+    // Computes the payout owed to each bet (same order as `bets`).
+    pub fn resolve_market(&self, bets: &[Bet], outcome: &SettlementOutcome) -> Result<Vec<f64>, MarketError> {
+        let weights = match self.outcome_weights(outcome)? {
+            Some(weights) => weights,
+            None => return self.stakes(bets),
+        };
+
+        match self.config.payout_model {
+            PayoutModel::Lmsr => {
+                self.validate_bets(bets)?;
+                Ok(bets.iter().map(|bet| bet.amount * weights[bet.option_id]).collect())
+            }
+            PayoutModel::Parimutuel => self.parimutuel_payouts(bets, &weights),
+        }
+    }
+
+    // Computes every payout up front and records them as pending
+    pub fn prepare_settlement(
+        &self,
+        market_id: &str,
+        bets: &[Bet],
+        outcome: &SettlementOutcome
+    ) -> Result<SettlementLedger, MarketError> {
+        let payouts = self.resolve_market(bets, outcome)?;
+
+        Ok(SettlementLedger {
+            market_id: market_id.to_string(),
+            outcome: outcome.clone(),
+            payouts: payouts.into_iter()
+                .enumerate()
+                .map(|(position, amount)| PositionPayout { position, amount, status: PayoutStatus::Pending })
+                .collect(),
+        })
+    }
+
+    // Asks the configured oracle for the outcome and prepares the ledger
+    pub fn auto_resolve(&self, market_id: &str, bets: &[Bet]) -> Result<SettlementLedger, MarketError> {
+        let oracle = self.oracle.as_ref()
+            .ok_or_else(|| MarketError::InvalidSettlement("No oracle configured".to_string()))?;
+        let outcome = oracle.resolve(market_id)?;
+
+        self.prepare_settlement(market_id, bets, &outcome)
+    }
+
+    // Pays the still-pending positions in order, marking each paid as soon as
+    // `pay` succeeds. Stops at the first failure, leaving the rest pending.
+    // Returns how many positions were paid; a completed ledger pays none.
+    pub fn resume_settlement<F>(&self, ledger: &mut SettlementLedger, mut pay: F) -> Result<usize, MarketError>
+    where
+        F: FnMut(&str, &PositionPayout) -> Result<(), MarketError>,
+    {
+        let mut paid = 0;
+        for payout in ledger.payouts.iter_mut().filter(|payout| payout.status == PayoutStatus::Pending) {
+            pay(&ledger.market_id, payout)?;
+            payout.status = PayoutStatus::Paid;
+            paid += 1;
+        }
+
+        Ok(paid)
+    }
+
+    // Fee withheld from the pool when a parimutuel market settles
+    pub fn settlement_fee(&self, bets: &[Bet], outcome: &SettlementOutcome) -> f64 {
+        match (self.config.payout_model, outcome) {
+            (PayoutModel::Parimutuel, SettlementOutcome::Winner(_) | SettlementOutcome::Split(_)) => {
+                Self::pool(bets) * self.config.fee_bps as f64 / 10_000.0
+            }
+            _ => 0.0,
+        }
+    }
+
+    // Books the settlement fee into `ledger` under `market_id`
+    pub fn record_settlement_fee(
+        &self,
+        market_id: &str,
+        bets: &[Bet],
+        outcome: &SettlementOutcome,
+        ledger: &mut FeeLedger
+    ) -> Result<FeeSplit, MarketError> {
+        self.validate_bets(bets)?;
+        ledger.record(market_id, self.settlement_fee(bets, outcome))
+    }
+
+    // Total stake paid into the market by `bets`.
+    pub fn collected_collateral(&self, bets: &[Bet]) -> Result<f64, MarketError> {
+        Ok(self.stakes(bets)?.iter().sum())
+    }
+
+    // What each bet paid: its LMSR cost, or the amount itself in a parimutuel pool
+    fn stakes(&self, bets: &[Bet]) -> Result<Vec<f64>, MarketError> {
+        self.validate_bets(bets)?;
+
+        match self.config.payout_model {
+            PayoutModel::Lmsr => trade_costs(&self.config, bets),
+            PayoutModel::Parimutuel => Ok(bets.iter().map(|bet| bet.amount).collect()),
+        }
+    }
+
+    fn pool(bets: &[Bet]) -> f64 {
+        bets.iter().map(|bet| bet.amount).sum()
+    }
+
+    // Each outcome's weighted share of the pool (net of fees) goes to its
+    // bettors pro rata; shares with no bettors are refunded to everyone
+    fn parimutuel_payouts(&self, bets: &[Bet], weights: &[f64]) -> Result<Vec<f64>, MarketError> {
+        self.validate_bets(bets)?;
+
+        let total_pool = Self::pool(bets);
+        if total_pool <= 0.0 {
+            return Ok(vec![0.0; bets.len()]);
+        }
+        let net_pool = total_pool * (1.0 - self.config.fee_bps as f64 / 10_000.0);
+
+        let mut outcome_pools = vec![0.0; self.config.num_outcomes];
+        for bet in bets {
+            outcome_pools[bet.option_id] += bet.amount;
+        }
+
+        let unclaimed: f64 = weights.iter()
+            .zip(&outcome_pools)
+            .filter(|(_, &pool)| pool <= 0.0)
+            .map(|(&weight, _)| weight)
+            .sum();
+
+        Ok(bets.iter()
+            .map(|bet| {
+                // Zero stakes can sit on an empty pool; they win nothing
+                let pool = outcome_pools[bet.option_id];
+                let winnings = if pool > 0.0 { weights[bet.option_id] * net_pool * bet.amount / pool } else { 0.0 };
+                let refund = unclaimed * net_pool * bet.amount / total_pool;
+                winnings + refund
+            })
+            .collect())
+    }
+
+    // Per-outcome payout per share, or None for a void market
+    fn outcome_weights(&self, outcome: &SettlementOutcome) -> Result<Option<Vec<f64>>, MarketError> {
+        let mut weights = vec![0.0; self.config.num_outcomes];
+
+        match outcome {
+            SettlementOutcome::Void => return Ok(None),
+            SettlementOutcome::Winner(index) => {
+                if *index >= self.config.num_outcomes {
+                    return Err(MarketError::InvalidOutcomeIndex(*index));
+                }
+                weights[*index] = 1.0;
+            }
+            SettlementOutcome::Split(splits) => {
+                for &(index, weight) in splits {
+                    if index >= self.config.num_outcomes {
+                        return Err(MarketError::InvalidOutcomeIndex(index));
+                    }
+                    if !weight.is_finite() || weight < 0.0 {
+                        return Err(MarketError::InvalidSettlement(
+                            format!("Invalid weight {} for outcome {}", weight, index)
+                        ));
+                    }
+                    weights[index] += weight;
+                }
+
+                let total: f64 = weights.iter().sum();
+                if (total - 1.0).abs() > WEIGHT_TOLERANCE {
+                    return Err(MarketError::InvalidSettlement(
+                        format!("Split weights sum to {}, expected 1", total)
+                    ));
+                }
+            }
+        }
+
+        Ok(Some(weights))
+    }
+
+    fn validate_bets(&self, bets: &[Bet]) -> Result<(), MarketError> {
+        for bet in bets {
+            if bet.option_id >= self.config.num_outcomes {
+                return Err(MarketError::InvalidOutcomeIndex(bet.option_id));
+            }
+            if !bet.amount.is_finite() || bet.amount < 0.0 {
+                return Err(MarketError::InvalidSettlement(
+                    format!("Invalid bet amount {}", bet.amount)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use rust_decimal::prelude::*;
+use rust_lmsr::{AdaptiveConfig, Bet, MarketConfig, MarketType, ProbabilityEngine};
+
+// Move in outcome 1's price from a 10-unit bet after `volume` has been bet on outcome 0
+fn impact_after(config: &MarketConfig, volume: f64) -> f64 {
+    let engine = ProbabilityEngine::new(config.clone());
+    let mut bets = vec![Bet { option_id: 0, amount: volume }];
+    let before = engine.calculate_price(&bets, 1).unwrap().to_f64().unwrap();
+    bets.push(Bet { option_id: 1, amount: 10.0 });
+    let after = engine.calculate_price(&bets, 1).unwrap().to_f64().unwrap();
+    after - before
+}
+
+fn lmsr_config(volume_factor: f64) -> MarketConfig {
+    MarketConfig {
+        adaptive_liquidity: Some(AdaptiveConfig { volume_factor }),
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    }
+}
+
+#[test]
+fn test_adaptive_liquidity_keeps_bets_moving_the_price() {
+    let fixed = lmsr_config(0.0);
+    let adaptive = lmsr_config(1.0);
+
+    let fixed_decay = impact_after(&fixed, 1_000.0) / impact_after(&fixed, 0.0);
+    let adaptive_decay = impact_after(&adaptive, 1_000.0) / impact_after(&adaptive, 0.0);
+
+    // Fixed b saturates the price, so later bets barely register; a growing
+    // b keeps the price interior and the same bet still moves it
+    assert!(fixed_decay < 0.001, "Fixed b impact ratio {}", fixed_decay);
+    assert!(adaptive_decay > 0.1, "Adaptive b impact ratio {}", adaptive_decay);
+}
+
+#[test]
+fn test_zero_volume_factor_matches_fixed_lmsr() {
+    let engine = ProbabilityEngine::new(lmsr_config(0.0));
+    let bets = [Bet { option_id: 0, amount: 100.0 }];
+
+    // Classic LMSR: p0 = e^(100/100) / (e^1 + 1)
+    let expected = 1f64.exp() / (1f64.exp() + 1.0);
+    let p0 = engine.calculate_price(&bets, 0).unwrap().to_f64().unwrap();
+    assert!((p0 - expected).abs() < 1e-12);
+}
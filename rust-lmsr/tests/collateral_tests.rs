@@ -0,0 +1,55 @@
+use rust_lmsr::{AdaptiveConfig, Bet, MarketConfig, MarketType, PredictionMarketEngine};
+
+// Reference LMSR cost C(q) = b·ln Σ exp(q_i / b)
+fn lmsr_cost(quantities: &[f64], liquidity: f64) -> f64 {
+    liquidity * quantities.iter().map(|q| (q / liquidity).exp()).sum::<f64>().ln()
+}
+
+#[test]
+fn test_total_collateral_matches_cost_difference() {
+    let engine = PredictionMarketEngine::try_new(50.0, 3, MarketType::Categorical).unwrap();
+    let bets = vec![
+        Bet { option_id: 0, amount: 40.0 },
+        Bet { option_id: 2, amount: 15.0 },
+        Bet { option_id: 0, amount: 25.0 },
+        Bet { option_id: 1, amount: 5.0 },
+    ];
+
+    let collateral = engine.total_collateral(&bets).unwrap();
+    let expected = lmsr_cost(&[65.0, 5.0, 15.0], 50.0) - lmsr_cost(&[0.0; 3], 50.0);
+
+    assert!((collateral - expected).abs() < 1e-9, "{} vs {}", collateral, expected);
+    assert!(collateral > 0.0);
+}
+
+#[test]
+fn test_total_collateral_of_empty_market_is_zero() {
+    let engine = PredictionMarketEngine::try_new(50.0, 2, MarketType::Binary).unwrap();
+
+    assert_eq!(engine.total_collateral(&[]).unwrap(), 0.0);
+    assert!(engine.total_collateral(&[Bet { option_id: 2, amount: 1.0 }]).is_err());
+}
+
+#[test]
+fn test_total_collateral_uses_effective_liquidity() {
+    let bets = vec![Bet { option_id: 0, amount: 30.0 }, Bet { option_id: 1, amount: 20.0 }];
+
+    // Outcome 0 trades against b = 10, outcome 1 against b = 40
+    let per_outcome = PredictionMarketEngine::try_from_config(MarketConfig {
+        outcome_liquidity: Some(vec![10.0, 40.0]),
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    }).unwrap();
+    let log_partition = |q0: f64, q1: f64| ((q0 / 10.0).exp() + (q1 / 40.0).exp()).ln();
+    let expected = 10.0 * (log_partition(30.0, 0.0) - log_partition(0.0, 0.0))
+        + 40.0 * (log_partition(30.0, 20.0) - log_partition(30.0, 0.0));
+    assert!((per_outcome.total_collateral(&bets).unwrap() - expected).abs() < 1e-9);
+
+    // The second bet is priced at the depth the first one reached: b = 50 + 0.5·30
+    let adaptive = PredictionMarketEngine::try_from_config(MarketConfig {
+        adaptive_liquidity: Some(AdaptiveConfig { volume_factor: 0.5 }),
+        ..MarketConfig::new(50.0, 2, MarketType::Binary)
+    }).unwrap();
+    let expected = (lmsr_cost(&[30.0, 0.0], 50.0) - lmsr_cost(&[0.0, 0.0], 50.0))
+        + (lmsr_cost(&[30.0, 20.0], 65.0) - lmsr_cost(&[30.0, 0.0], 65.0));
+    assert!((adaptive.total_collateral(&bets).unwrap() - expected).abs() < 1e-9);
+}
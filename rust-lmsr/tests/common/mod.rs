@@ -0,0 +1,9 @@
+// Small LCG so randomized tests are varied but reproducible; yields
+// uniform values in [0, 1)
+pub fn lcg(seed: u64) -> impl FnMut() -> f64 {
+    let mut state = seed;
+    move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,33 @@
+use rust_lmsr::{MarketConfig, MarketError, MarketType, PredictionMarketEngine, DEFAULT_LIQUIDITY_PARAM};
+
+#[test]
+fn test_non_positive_or_non_finite_liquidity_rejected() {
+    for liquidity in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+        let result = PredictionMarketEngine::try_new(liquidity, 2, MarketType::Binary);
+        assert!(
+            matches!(result, Err(MarketError::InvalidLiquidity(_))),
+            "Liquidity {} should be rejected",
+            liquidity
+        );
+    }
+}
+
+#[test]
+fn test_positive_liquidity_accepted() {
+    let engine = PredictionMarketEngine::try_new(0.5, 2, MarketType::Binary)
+        .expect("Small positive liquidity is still valid");
+    assert_eq!(engine.config().liquidity_param, 0.5);
+
+    let engine = PredictionMarketEngine::with_default_liquidity(3, MarketType::Categorical);
+    assert_eq!(engine.config().liquidity_param, DEFAULT_LIQUIDITY_PARAM);
+}
+
+#[test]
+fn test_state_with_invalid_liquidity_rejected() {
+    let state = serde_json::json!({
+        "config": MarketConfig::new(0.0, 2, MarketType::Binary),
+        "bets": [],
+    });
+
+    assert!(PredictionMarketEngine::from_state(&state.to_string()).is_err());
+}
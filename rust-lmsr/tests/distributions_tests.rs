@@ -0,0 +1,47 @@
+use rust_lmsr::{cross_entropy, kl_divergence};
+
+#[test]
+fn test_kl_divergence_matches_hand_computed_value() {
+    // 0.5·ln(0.5/0.25) + 0.5·ln(0.5/0.75) = 0.5·ln(4/3)
+    let kl = kl_divergence(&[0.5, 0.5], &[0.25, 0.75]).expect("KL should be finite");
+    assert!((kl - 0.5 * (4.0f64 / 3.0).ln()).abs() < 1e-12);
+
+    // Outcomes with zero probability under p contribute nothing
+    let kl = kl_divergence(&[1.0, 0.0], &[0.5, 0.5]).expect("KL should be finite");
+    assert!((kl - 2f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn test_identical_distributions_have_zero_kl() {
+    let p = [0.2, 0.3, 0.5];
+
+    assert!(kl_divergence(&p, &p).unwrap().abs() < 1e-12);
+
+    // Cross-entropy then reduces to the entropy of p
+    let entropy: f64 = -p.iter().map(|v| v * v.ln()).sum::<f64>();
+    assert!((cross_entropy(&p, &p).unwrap() - entropy).abs() < 1e-12);
+}
+
+#[test]
+fn test_invalid_distributions_rejected() {
+    // q rules out an outcome p considers possible
+    assert!(kl_divergence(&[0.5, 0.5], &[1.0, 0.0]).is_err());
+    assert!(cross_entropy(&[0.5, 0.5], &[1.0, 0.0]).is_err());
+
+    assert!(kl_divergence(&[0.5, 0.5], &[0.2, 0.3, 0.5]).is_err());
+    assert!(kl_divergence(&[0.5, 0.6], &[0.5, 0.5]).is_err());
+}
+
+#[test]
+fn test_joint_probability_of_independent_markets() {
+    use rust_lmsr::joint_probability;
+
+    let first = [0.5, 0.5];
+    let second = [0.2, 0.5, 0.3];
+
+    assert_eq!(joint_probability(&[&first, &second], &[0, 1]).unwrap(), 0.25);
+    assert!((joint_probability(&[&first, &second], &[1, 2]).unwrap() - 0.15).abs() < 1e-12);
+
+    assert!(joint_probability(&[&first, &second], &[0, 3]).is_err());
+    assert!(joint_probability(&[&first, &second], &[0]).is_err());
+}
@@ -0,0 +1,104 @@
+use rust_lmsr::{
+    Bet, BetParseError, MarketConfig, MarketMakerEngine, MarketType, ParsedBets, PredictionMarketEngine,
+    ProbabilityEngine, ProbabilityFormat, RiskAssessmentEngine,
+};
+
+// Per-entry parse results as the wasm layer produces them from JS values
+fn parse(entries: &[&str]) -> Vec<Result<Bet, serde_json::Error>> {
+    entries.iter().map(|entry| serde_json::from_str(entry)).collect()
+}
+
+fn engine(num_outcomes: usize, market_type: MarketType) -> PredictionMarketEngine {
+    PredictionMarketEngine::try_new(10.0, num_outcomes, market_type).unwrap()
+}
+
+#[test]
+fn test_analyze_market_matches_individual_calls() {
+    let config = MarketConfig::new(10.0, 3, MarketType::Categorical);
+    let bets = vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 30.0 },
+        Bet { option_id: 2, amount: 5.0 },
+    ];
+
+    let analysis = engine(3, MarketType::Categorical).analyze(&bets).expect("Market analysis should succeed");
+
+    let probabilities = ProbabilityEngine::new(config.clone()).calculate_probabilities(&bets).unwrap();
+    assert_eq!(analysis.probabilities, probabilities.to_f64_vec());
+    assert_eq!(analysis.strategy, MarketMakerEngine::new(config.clone()).simulate_strategy(&bets).unwrap());
+    assert_eq!(analysis.risk, RiskAssessmentEngine::new(config).assess_risk(&bets).unwrap());
+}
+
+#[test]
+fn test_parse_bets_all_valid() {
+    let entries = [r#"{"option_id": 0, "amount": 50.0}"#, r#"{"option_id": 1, "amount": 30.0}"#];
+
+    assert_eq!(ParsedBets::strict(parse(&entries)).unwrap().len(), 2);
+
+    let parsed = ParsedBets::lenient(parse(&entries));
+    assert_eq!(parsed.bets.len(), 2);
+    assert!(parsed.rejected.is_empty());
+}
+
+#[test]
+fn test_parse_bets_with_one_malformed_entry() {
+    let entries = [r#"{"option_id": 0, "amount": 50.0}"#, r#""not a bet""#, r#"{"option_id": 1, "amount": 30.0}"#];
+
+    let error = ParsedBets::strict(parse(&entries)).unwrap_err();
+    assert_eq!(error.index, 1);
+
+    let parsed = ParsedBets::lenient(parse(&entries));
+    assert_eq!(parsed.bets.len(), 2);
+    assert_eq!(parsed.rejected.len(), 1);
+    assert_eq!(parsed.rejected[0].index, 1);
+}
+
+#[test]
+fn test_parse_bets_empty_array() {
+    assert!(ParsedBets::strict(parse(&[])).unwrap().is_empty());
+
+    let parsed = ParsedBets::lenient(parse(&[]));
+    assert!(parsed.bets.is_empty() && parsed.rejected.is_empty());
+}
+
+#[test]
+fn test_probability_formats_agree() {
+    let engine = engine(3, MarketType::Categorical);
+    let bets = vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 30.0 },
+    ];
+
+    let raw = engine.formatted_probabilities(&bets, ProbabilityFormat::Raw).unwrap();
+    let default = engine.formatted_probabilities(&bets, ProbabilityFormat::default()).unwrap();
+    let percent = engine.formatted_probabilities(&bets, ProbabilityFormat::Percentage).unwrap();
+    let bps = engine.formatted_probabilities(&bets, ProbabilityFormat::BasisPoints).unwrap();
+
+    assert_eq!(raw, default, "Raw must remain the default format");
+    for i in 0..raw.len() {
+        assert_eq!(raw[i], (raw[i] * 1e6).round() / 1e6, "Raw should be rounded to 1e-6");
+        assert!((raw[i] * 100.0 - percent[i]).abs() < 1e-12);
+        assert!((raw[i] * 10_000.0 - bps[i]).abs() < 1e-10);
+    }
+
+    let price = engine.formatted_price(&bets, 1, ProbabilityFormat::BasisPoints).unwrap();
+    assert_eq!(price, bps[1]);
+}
+
+#[test]
+fn test_validate_bets_reports_every_problem() {
+    let engine = engine(2, MarketType::Binary);
+    let valid = [r#"{"option_id": 0, "amount": 50.0}"#, r#"{"option_id": 1, "amount": 30.0}"#];
+    assert!(engine.validate_parsed(parse(&valid)).is_ok());
+
+    let batch = [
+        r#"{"option_id": 0, "amount": 50.0}"#,
+        r#"{"option_id": 5, "amount": 10.0}"#,
+        r#"{"option_id": 1, "amount": -3.0}"#,
+        r#"{"amount": 1.0}"#,
+    ];
+    let problems: Vec<BetParseError> = engine.validate_parsed(parse(&batch)).unwrap_err();
+
+    let indices: Vec<usize> = problems.iter().map(|problem| problem.index).collect();
+    assert_eq!(indices, vec![1, 2, 3]);
+}
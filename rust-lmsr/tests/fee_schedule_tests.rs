@@ -0,0 +1,141 @@
+use rust_lmsr::{
+    Bet, FeeDistribution, FeeLedger, FeeSchedule, FeeSplit, FeeTier, MarketConfig, MarketError, MarketType,
+    PayoutModel, PredictionMarketEngine, SettlementEngine, SettlementOutcome,
+};
+
+fn tiered() -> FeeSchedule {
+    FeeSchedule::new(vec![
+        FeeTier { min_volume: 0.0, fee_bps: 30 },
+        FeeTier { min_volume: 10_000.0, fee_bps: 20 },
+        FeeTier { min_volume: 100_000.0, fee_bps: 10 },
+    ]).expect("Monotonic schedule should be accepted")
+}
+
+#[test]
+fn test_crossing_threshold_moves_user_to_lower_tier() {
+    let schedule = tiered();
+
+    assert_eq!(schedule.fee_for(0.0), 30);
+    assert_eq!(schedule.fee_for(9_999.99), 30);
+    assert_eq!(schedule.fee_for(10_000.0), 20);
+    assert_eq!(schedule.fee_for(250_000.0), 10);
+
+    assert!((schedule.fee_on(1_000.0, 9_999.0) - 3.0).abs() < 1e-12);
+    assert!((schedule.fee_on(1_000.0, 10_001.0) - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_trade_cost_charges_the_users_tier() {
+    let config = MarketConfig {
+        taker_fee_bps: 50,
+        fee_schedule: Some(tiered()),
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    };
+    let engine = PredictionMarketEngine::try_from_config(config.clone()).unwrap();
+    let bets = vec![Bet { option_id: 0, amount: 40.0 }];
+    let bet = Bet { option_id: 1, amount: 25.0 };
+
+    let newcomer = engine.trade_cost(&bets, &bet, 0.0).unwrap();
+    let regular = engine.trade_cost(&bets, &bet, 10_000.0).unwrap();
+
+    // The LMSR cost is the same for both; only the fee tier differs
+    let expected = engine.total_collateral(&[bets[0].clone(), bet.clone()]).unwrap() - engine.total_collateral(&bets).unwrap();
+    assert!((newcomer.cost - expected).abs() < 1e-9);
+    assert_eq!(regular.cost, newcomer.cost);
+    assert!((newcomer.fee - newcomer.cost * 0.003).abs() < 1e-12);
+    assert!((regular.fee - regular.cost * 0.002).abs() < 1e-12);
+    assert!((regular.total() - regular.cost - regular.fee).abs() < 1e-12);
+
+    // Without a schedule the flat taker fee applies
+    let flat = PredictionMarketEngine::try_from_config(MarketConfig { fee_schedule: None, ..config }).unwrap();
+    let charged = flat.trade_cost(&bets, &bet, 10_000.0).unwrap();
+    assert!((charged.fee - charged.cost * 0.005).abs() < 1e-12);
+}
+
+#[test]
+fn test_non_monotonic_schedule_rejected() {
+    let rising_fee = FeeSchedule::new(vec![
+        FeeTier { min_volume: 0.0, fee_bps: 20 },
+        FeeTier { min_volume: 10_000.0, fee_bps: 25 },
+    ]);
+    assert!(matches!(rising_fee, Err(MarketError::InvalidFeeSchedule(_))));
+
+    let unordered = FeeSchedule::new(vec![
+        FeeTier { min_volume: 0.0, fee_bps: 30 },
+        FeeTier { min_volume: 50_000.0, fee_bps: 20 },
+        FeeTier { min_volume: 10_000.0, fee_bps: 10 },
+    ]);
+    assert!(unordered.is_err());
+    assert!(FeeSchedule::new(Vec::new()).is_err());
+
+    // Deserialization goes through the same validation
+    let json = r#"[{"min_volume": 0.0, "fee_bps": 10}, {"min_volume": 5.0, "fee_bps": 50}]"#;
+    assert!(serde_json::from_str::<FeeSchedule>(json).is_err());
+}
+
+#[test]
+fn test_fee_distribution_must_cover_whole_fee() {
+    assert!(FeeDistribution::new(7_000, 3_000).is_ok());
+    assert!(matches!(FeeDistribution::new(7_000, 2_000), Err(MarketError::InvalidFeeSchedule(_))));
+    assert!(FeeLedger::new(FeeDistribution { treasury_bps: 10_000, burn_bps: 1 }).is_err());
+    assert!(FeeDistribution::new(u32::MAX, 10_001).is_err());
+
+    // Deserialization goes through the same validation
+    let json = r#"{"treasury_bps": 7000, "burn_bps": 2000}"#;
+    assert!(serde_json::from_str::<FeeDistribution>(json).is_err());
+    let json = r#"{"distribution": {"treasury_bps": 7000, "burn_bps": 2000}, "markets": {}}"#;
+    assert!(serde_json::from_str::<FeeLedger>(json).is_err());
+    let json = r#"{"distribution": {"treasury_bps": 7000, "burn_bps": 3000}, "markets": {"m": {"treasury": -1.0, "burn": 0.0}}}"#;
+    assert!(serde_json::from_str::<FeeLedger>(json).is_err());
+
+    let mut ledger = FeeLedger::new(FeeDistribution::new(7_000, 3_000).unwrap()).unwrap();
+    ledger.record("market-1", 10.0).unwrap();
+    let restored: FeeLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+    assert_eq!(restored, ledger);
+}
+
+#[test]
+fn test_settlement_fees_split_between_treasury_and_burn() {
+    let config = MarketConfig {
+        payout_model: PayoutModel::Parimutuel,
+        fee_bps: 200,
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    };
+    let engine = SettlementEngine::new(config);
+    let bets = vec![Bet { option_id: 0, amount: 300.0 }, Bet { option_id: 1, amount: 200.0 }];
+    let mut ledger = FeeLedger::new(FeeDistribution::new(7_000, 3_000).unwrap()).unwrap();
+
+    let split = engine.record_settlement_fee("market-1", &bets, &SettlementOutcome::Winner(0), &mut ledger).unwrap();
+
+    // 2% of the 500 pool
+    assert!((split.total() - 10.0).abs() < 1e-12);
+    assert!((split.treasury - 7.0).abs() < 1e-12);
+    assert!((split.burn - 3.0).abs() < 1e-12);
+
+    ledger.record("market-1", 1.0).unwrap();
+    let market = ledger.market("market-1");
+    assert!((market.treasury - 7.7).abs() < 1e-12);
+    assert!((market.burn - 3.3).abs() < 1e-12);
+    assert_eq!(ledger.market("market-2"), FeeSplit::default());
+}
+
+#[test]
+fn test_trade_fees_split_per_market() {
+    // Default LMSR payout, where settlement itself collects nothing
+    let config = MarketConfig { taker_fee_bps: 100, ..MarketConfig::new(100.0, 2, MarketType::Binary) };
+    let engine = PredictionMarketEngine::try_from_config(config).unwrap();
+    let mut ledger = FeeLedger::new(FeeDistribution::new(7_000, 3_000).unwrap()).unwrap();
+    let bets = vec![Bet { option_id: 0, amount: 40.0 }];
+    let bet = Bet { option_id: 1, amount: 25.0 };
+
+    let trade = engine.record_trade_fee("market-1", &bets, &bet, 0.0, &mut ledger).unwrap();
+    assert_eq!(trade, engine.trade_cost(&bets, &bet, 0.0).unwrap());
+    assert!(trade.fee > 0.0);
+
+    engine.record_trade_fee("market-2", &[], &bet, 0.0, &mut ledger).unwrap();
+    let market = ledger.market("market-1");
+    assert!((market.total() - trade.fee).abs() < 1e-12);
+    assert!((market.treasury - trade.fee * 0.7).abs() < 1e-12);
+    assert!((market.burn - trade.fee * 0.3).abs() < 1e-12);
+    assert_ne!(ledger.market("market-2"), FeeSplit::default());
+}
@@ -0,0 +1,300 @@
+// LMSR parity fixture shared by the parity tests of rust-lmsr and wasm-lmsr
+// (pulled in with `include!`), so every implementation is held to the same
+// golden values and none can drift without failing CI.
+//
+// 24 cases over 2-8 outcomes, drawn from a seeded generator. Each lists the
+// liquidity `b`, the volume bet on each outcome, the LMSR prices
+// p_i = exp(q_i / b) / Σ exp(q_j / b) and their entropy -Σ p_i ln p_i, all
+// computed independently of either crate in f64.
+
+pub struct ParityCase {
+    pub liquidity: f64,
+    pub volumes: &'static [f64],
+    pub probabilities: &'static [f64],
+    pub entropy: f64,
+}
+
+pub const PARITY_CASES: &[ParityCase] = &[
+    ParityCase {
+        liquidity: 128.95688768155588,
+        volumes: &[133.0, 140.0],
+        probabilities: &[
+            0.4864329043638667,
+            0.5135670956361333,
+        ],
+        entropy: 0.6927790032048717,
+    },
+    ParityCase {
+        liquidity: 381.1952869194399,
+        volumes: &[43.0, 78.0, 192.0],
+        probabilities: &[
+            0.2797642234857656,
+            0.30666736075418843,
+            0.41356841576004605,
+        ],
+        entropy: 1.083997107967257,
+    },
+    ParityCase {
+        liquidity: 78.85216420319121,
+        volumes: &[96.0, 81.0, 189.0, 70.0],
+        probabilities: &[
+            0.17246192528957305,
+            0.14258625112975473,
+            0.5609314907475669,
+            0.12402033283310535,
+        ],
+        entropy: 1.1640211279489052,
+    },
+    ParityCase {
+        liquidity: 184.5327398835374,
+        volumes: &[125.0, 123.0, 43.0, 117.0, 165.0],
+        probabilities: &[
+            0.20703610479340093,
+            0.20480432522474948,
+            0.13275795811237873,
+            0.1982522989712113,
+            0.2571493128982596,
+        ],
+        entropy: 1.5889290005622545,
+    },
+    ParityCase {
+        liquidity: 480.0378052441682,
+        volumes: &[191.0, 51.0, 111.0, 96.0, 170.0, 98.0],
+        probabilities: &[
+            0.1924908753903733,
+            0.14379735557232723,
+            0.1629421469560405,
+            0.15792933280376928,
+            0.1842515968067019,
+            0.15858869247078777,
+        ],
+        entropy: 1.786837902343215,
+    },
+    ParityCase {
+        liquidity: 482.8972335883943,
+        volumes: &[114.0, 169.0, 81.0, 133.0, 161.0, 15.0, 68.0],
+        probabilities: &[
+            0.14449386562350575,
+            0.16192494462673462,
+            0.13494935135439562,
+            0.1502924255328188,
+            0.15926448553088055,
+            0.11771006095117975,
+            0.13136486638048486,
+        ],
+        entropy: 1.940528923149857,
+    },
+    ParityCase {
+        liquidity: 126.80602273699137,
+        volumes: &[167.0, 131.0, 132.0, 44.0, 71.0, 59.0, 129.0, 4.0],
+        probabilities: &[
+            0.2081570898119618,
+            0.1567096682840983,
+            0.15795037596795314,
+            0.07890996829048483,
+            0.09763452798554162,
+            0.0888188117935605,
+            0.15425741381538177,
+            0.057562144051018144,
+        ],
+        entropy: 2.0038698044713037,
+    },
+    ParityCase {
+        liquidity: 408.02398786531415,
+        volumes: &[128.0, 163.0],
+        probabilities: &[
+            0.47856832211852185,
+            0.5214316778814783,
+        ],
+        entropy: 0.6922282654234668,
+    },
+    ParityCase {
+        liquidity: 301.5970386244603,
+        volumes: &[104.0, 61.0, 105.0],
+        probabilities: &[
+            0.3483781273385533,
+            0.3020867169800201,
+            0.3495351556814266,
+        ],
+        entropy: 1.0963776842869686,
+    },
+    ParityCase {
+        liquidity: 368.72471930632514,
+        volumes: &[79.0, 120.0, 86.0, 174.0],
+        probabilities: &[
+            0.2257001816229281,
+            0.2522451803554329,
+            0.23002588381234637,
+            0.2920287542092926,
+        ],
+        entropy: 1.3808930465369675,
+    },
+    ParityCase {
+        liquidity: 412.1637217133882,
+        volumes: &[177.0, 123.0, 114.0, 177.0, 187.0],
+        probabilities: &[
+            0.2100831689631995,
+            0.18428576017174256,
+            0.18030531606789432,
+            0.2100831689631995,
+            0.215242585833964,
+        ],
+        entropy: 1.6067338955701276,
+    },
+    ParityCase {
+        liquidity: 98.52348794951158,
+        volumes: &[22.0, 110.0, 75.0, 99.0, 174.0, 25.0],
+        probabilities: &[
+            0.07663593082501116,
+            0.18721432180201494,
+            0.1312375221620295,
+            0.16743673239373832,
+            0.3584701382514519,
+            0.07900535456575422,
+        ],
+        entropy: 1.6445689492640294,
+    },
+    ParityCase {
+        liquidity: 342.88619850252667,
+        volumes: &[91.0, 141.0, 13.0, 197.0, 109.0, 175.0, 93.0],
+        probabilities: &[
+            0.1306656106795569,
+            0.15117867949899186,
+            0.10408009506141584,
+            0.17799971299762252,
+            0.13770820923740995,
+            0.1669377032907653,
+            0.13142998923423774,
+        ],
+        entropy: 1.9328261375625821,
+    },
+    ParityCase {
+        liquidity: 329.6180622234781,
+        volumes: &[56.0, 9.0, 186.0, 155.0, 144.0, 121.0, 156.0, 195.0],
+        probabilities: &[
+            0.09897166380575946,
+            0.08581933335219566,
+            0.14682324489894338,
+            0.13364422121723188,
+            0.1292578499103172,
+            0.1205460112695933,
+            0.1340502886255451,
+            0.15088738692041417,
+        ],
+        entropy: 2.0645346739256834,
+    },
+    ParityCase {
+        liquidity: 156.30258034387637,
+        volumes: &[141.0, 187.0],
+        probabilities: &[
+            0.42695124755109026,
+            0.5730487524489097,
+        ],
+        entropy: 0.6824366466205107,
+    },
+    ParityCase {
+        liquidity: 168.51106834042983,
+        volumes: &[102.0, 4.0, 53.0],
+        probabilities: &[
+            0.4335193966339441,
+            0.24234743226196612,
+            0.32413317110408985,
+        ],
+        entropy: 1.0710114430867388,
+    },
+    ParityCase {
+        liquidity: 463.0351615233171,
+        volumes: &[198.0, 171.0, 72.0, 138.0],
+        probabilities: &[
+            0.27905010695483917,
+            0.26324376184483234,
+            0.21257052102318794,
+            0.24513561017714058,
+        ],
+        entropy: 1.3813226945325359,
+    },
+    ParityCase {
+        liquidity: 342.72400178302576,
+        volumes: &[149.0, 15.0, 67.0, 136.0, 73.0],
+        probabilities: &[
+            0.2365468091862364,
+            0.1599979414403148,
+            0.18621213510346152,
+            0.22774229771404547,
+            0.18950081655594186,
+        ],
+        entropy: 1.599379940337413,
+    },
+    ParityCase {
+        liquidity: 499.3219217131561,
+        volumes: &[41.0, 123.0, 1.0, 7.0, 64.0, 136.0],
+        probabilities: &[
+            0.1589226699582842,
+            0.18728667102796584,
+            0.14668817740137247,
+            0.14846145875726977,
+            0.16641425626181205,
+            0.19222676659329566,
+        ],
+        entropy: 1.7862027663487008,
+    },
+    ParityCase {
+        liquidity: 430.81131144827947,
+        volumes: &[55.0, 77.0, 75.0, 133.0, 147.0, 2.0, 61.0],
+        probabilities: &[
+            0.13450397778961234,
+            0.14155101940711992,
+            0.14089540542935236,
+            0.16120027163801376,
+            0.1665248155477485,
+            0.11893416168032228,
+            0.1363903485078309,
+        ],
+        entropy: 1.9403768674881137,
+    },
+    ParityCase {
+        liquidity: 264.0353572171349,
+        volumes: &[39.0, 143.0, 131.0, 189.0, 119.0, 143.0, 159.0, 22.0],
+        probabilities: &[
+            0.09079234908165486,
+            0.13462076294925526,
+            0.12863940799621698,
+            0.16024129184679833,
+            0.12292381150636407,
+            0.13462076294925526,
+            0.14303074494812967,
+            0.08513086872232553,
+        ],
+        entropy: 2.0605039971478063,
+    },
+    ParityCase {
+        liquidity: 275.0433587972637,
+        volumes: &[149.0, 137.0],
+        probabilities: &[
+            0.5109056412803508,
+            0.4890943587196493,
+        ],
+        entropy: 0.6929092956728024,
+    },
+    ParityCase {
+        liquidity: 201.35607373121903,
+        volumes: &[102.0, 176.0, 103.0],
+        probabilities: &[
+            0.2899298440637303,
+            0.4186968442129174,
+            0.29137331172335235,
+        ],
+        entropy: 1.0827947408194931,
+    },
+    ParityCase {
+        liquidity: 357.97521805675797,
+        volumes: &[13.0, 28.0, 8.0, 144.0],
+        probabilities: &[
+            0.2236735516545322,
+            0.2332451326881677,
+            0.22057112006735474,
+            0.32251019558994526,
+        ],
+        entropy: 1.372853269806439,
+    },
+];
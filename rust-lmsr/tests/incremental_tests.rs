@@ -0,0 +1,72 @@
+use rust_decimal::prelude::*;
+use rust_lmsr::{Bet, IncrementalMarket, MarketConfig, MarketType, ProbabilityEngine};
+
+mod common;
+
+#[test]
+fn test_incremental_matches_full_recompute() {
+    let config = MarketConfig::new(40.0, 3, MarketType::Categorical);
+    let bets = [
+        Bet { option_id: 1, amount: 12.0 },
+        Bet { option_id: 0, amount: 3.5 },
+        Bet { option_id: 1, amount: 0.25 },
+        Bet { option_id: 2, amount: 48.0 },
+    ];
+
+    let engine = ProbabilityEngine::new(config.clone());
+    let mut market = IncrementalMarket::new(config).unwrap();
+
+    for (i, bet) in bets.iter().enumerate() {
+        let incremental = market.apply_bet(bet).unwrap();
+        let full: Vec<f64> = engine.calculate_probabilities(&bets[..=i]).unwrap()
+            .iter()
+            .map(|p| p.to_f64().unwrap())
+            .collect();
+
+        assert_eq!(incremental, full, "Mismatch after bet {}", i);
+        let price = market.price(bet.option_id).unwrap();
+        assert!((price - full[bet.option_id]).abs() < 1e-12, "Price mismatch after bet {}", i);
+    }
+}
+
+#[test]
+fn test_rejected_bet_leaves_market_unchanged() {
+    let mut market = IncrementalMarket::new(MarketConfig::new(40.0, 2, MarketType::Binary)).unwrap();
+    market.apply_bet(&Bet { option_id: 0, amount: 10.0 }).unwrap();
+    let before = market.probabilities().unwrap();
+
+    assert!(market.apply_bet(&Bet { option_id: 2, amount: 10.0 }).is_err());
+    assert_eq!(market.probabilities().unwrap(), before);
+}
+
+#[test]
+fn test_incremental_partition_matches_full_recompute() {
+    let mut next = common::lcg(0x5851_f42d_4c95_7f2d);
+
+    let config = MarketConfig::new(50.0, 200, MarketType::Categorical);
+    let engine = ProbabilityEngine::new(config.clone());
+    let mut market = IncrementalMarket::new(config).unwrap();
+    let mut bets = Vec::new();
+
+    for i in 0..2_000 {
+        // Mostly buys, with the occasional sale to drag an outcome back down
+        let amount = if i % 7 == 0 { -next() } else { next() * 400.0 };
+        let bet = Bet { option_id: (next() * 200.0) as usize, amount };
+        // A sale larger than the outcome's total is rejected and not recorded
+        if market.record_bet(&bet).is_ok() {
+            bets.push(bet);
+        }
+
+        if i % 100 == 99 {
+            let full = engine.partition_function(&bets).unwrap();
+            let incremental = market.partition_function();
+            assert!(((incremental - full) / full).abs() < 1e-9, "{} vs {} after {} bets", incremental, full, i + 1);
+        }
+    }
+
+    let probabilities = engine.calculate_probabilities(&bets).unwrap();
+    for (outcome, p) in probabilities.iter().enumerate() {
+        assert!((market.price(outcome).unwrap() - p.to_f64().unwrap()).abs() < 1e-9);
+    }
+    assert!(market.price(200).is_err());
+}
@@ -0,0 +1,277 @@
+use rust_lmsr::{
+    AdaptiveConfig, Bet, MarketConfig, MarketMakerEngine, MarketType, PredictionMarketEngine, ProbabilityEngine,
+    RiskAssessmentEngine, Side,
+};
+
+fn sample_bets() -> Vec<Bet> {
+    vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 30.0 },
+    ]
+}
+
+#[test]
+fn test_zero_fees_keep_gross_spread() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+
+    let strategy = engine.simulate_strategy(&sample_bets())
+        .expect("Market making simulation should succeed");
+
+    let gross_spread: f64 = strategy.bid_prices().iter()
+        .zip(strategy.ask_prices())
+        .map(|(bid, ask)| ask - bid)
+        .sum::<f64>() / 2.0;
+    assert!((strategy.spread() - gross_spread).abs() < 1e-12);
+    assert!((strategy.net_edge() - gross_spread / 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_net_edge_increases_with_rebate() {
+    let mut previous_edge = f64::NEG_INFINITY;
+
+    for rebate in [0, 5, 25] {
+        let config = MarketConfig {
+            maker_rebate_bps: rebate,
+            taker_fee_bps: 30,
+            ..MarketConfig::new(10.0, 2, MarketType::Binary)
+        };
+        let strategy = MarketMakerEngine::new(config).simulate_strategy(&sample_bets())
+            .expect("Market making simulation should succeed");
+
+        assert!(strategy.net_edge() > previous_edge, "Rebate of {} bps should raise the edge", rebate);
+        previous_edge = strategy.net_edge();
+    }
+}
+
+#[test]
+fn test_taker_fee_is_deducted_from_net_edge() {
+    let free = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary))
+        .simulate_strategy(&sample_bets())
+        .unwrap();
+    let config = MarketConfig {
+        taker_fee_bps: 30,
+        ..MarketConfig::new(10.0, 2, MarketType::Binary)
+    };
+    let charged = MarketMakerEngine::new(config).simulate_strategy(&sample_bets()).unwrap();
+
+    // Flattening a fill pays 30 bps on the notional of both legs
+    let notional: f64 = free.bid_prices().iter()
+        .zip(free.ask_prices())
+        .map(|(bid, ask)| ask + bid)
+        .sum::<f64>() / 2.0;
+    assert_eq!(charged.bid_prices(), free.bid_prices());
+    assert!((free.spread() - charged.spread() - notional * 0.003).abs() < 1e-12);
+    assert!((free.net_edge() - charged.net_edge() - notional * 0.0015).abs() < 1e-12);
+}
+
+#[test]
+fn test_volume_to_move_even_market_to_seventy_percent() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+
+    let volume = engine.volume_to_reach(&[], 0, 0.7)
+        .expect("0.7 should be reachable from an even market");
+
+    // LMSR price after the move: 1 / (1 + e^(-Δq/b))
+    let moved_price = 1.0 / (1.0 + (-volume / 10.0).exp());
+    assert!((volume - 10.0 * (0.7f64 / 0.3).ln()).abs() < 1e-9);
+    assert!((moved_price - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn test_volume_to_reach_rejects_unreachable_targets() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+
+    assert!(engine.volume_to_reach(&[], 0, 1.0).is_err());
+    assert!(engine.volume_to_reach(&[], 0, 0.0).is_err());
+    // Moving down from 0.5 would need negative volume on the outcome
+    assert!(engine.volume_to_reach(&[], 0, 0.3).is_err());
+}
+
+#[test]
+fn test_volume_to_reach_uses_effective_liquidity() {
+    let per_outcome = MarketConfig {
+        outcome_liquidity: Some(vec![10.0, 40.0]),
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    };
+    let adaptive = MarketConfig {
+        adaptive_liquidity: Some(AdaptiveConfig { volume_factor: 0.1 }),
+        ..MarketConfig::new(10.0, 2, MarketType::Binary)
+    };
+
+    for config in [per_outcome, adaptive] {
+        let volume = MarketMakerEngine::new(config.clone()).volume_to_reach(&sample_bets(), 1, 0.7)
+            .expect("0.7 should be reachable");
+
+        let mut moved = sample_bets();
+        moved.push(Bet { option_id: 1, amount: volume });
+        let price = ProbabilityEngine::new(config).calculate_probabilities(&moved).unwrap().to_f64_vec()[1];
+        assert!((price - 0.7).abs() < 1e-9, "Moved price {}", price);
+    }
+}
+
+#[test]
+fn test_adaptive_liquidity_caps_reachable_price() {
+    // With b growing one-for-one with volume the price levels off near 1 / (1 + e^-1)
+    let config = MarketConfig {
+        adaptive_liquidity: Some(AdaptiveConfig { volume_factor: 1.0 }),
+        ..MarketConfig::new(10.0, 2, MarketType::Binary)
+    };
+    let engine = MarketMakerEngine::new(config);
+
+    assert!(engine.volume_to_reach(&[], 0, 0.7).is_ok());
+    assert!(engine.volume_to_reach(&[], 0, 0.9).is_err());
+}
+
+#[test]
+fn test_volatility_widens_spread() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+    let bets = sample_bets();
+
+    let base = engine.simulate_strategy(&bets).unwrap();
+    let calm = engine.simulate_strategy_with_volatility(&bets, 0.0).unwrap();
+    let volatile = engine.simulate_strategy_with_volatility(&bets, 0.8).unwrap();
+    let extreme = engine.simulate_strategy_with_volatility(&bets, 1_000.0).unwrap();
+
+    assert_eq!(calm, base, "Zero volatility should reproduce the base spread");
+    assert!(volatile.spread() > calm.spread());
+    for outcome in 0..2 {
+        let calm_width = calm.ask_prices()[outcome] - calm.bid_prices()[outcome];
+        let volatile_width = volatile.ask_prices()[outcome] - volatile.bid_prices()[outcome];
+        assert!(volatile_width > calm_width);
+    }
+    // Spread is clamped to max_half_spread, so it stops growing
+    let capped = engine.simulate_strategy_with_volatility(&bets, 2_000.0).unwrap();
+    assert_eq!(extreme.spread(), capped.spread());
+}
+
+#[test]
+fn test_backtest_balanced_stream_earns_spread() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+    let balanced: Vec<Bet> = (0..20)
+        .map(|i| Bet { option_id: i % 2, amount: 1.0 })
+        .collect();
+
+    let marked = engine.backtest_pnl(&balanced, None).unwrap();
+    let settled = engine.backtest_pnl(&balanced, Some(0)).unwrap();
+
+    assert!(marked > 0.0, "Spread revenue should be positive, got {}", marked);
+    assert!(settled > 0.0, "Balanced inventory should settle at a profit, got {}", settled);
+}
+
+#[test]
+fn test_backtest_one_sided_stream_loses_on_adverse_outcome() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+    let balanced: Vec<Bet> = (0..20)
+        .map(|i| Bet { option_id: i % 2, amount: 1.0 })
+        .collect();
+    let one_sided = vec![Bet { option_id: 0, amount: 10.0 }; 20];
+
+    let adverse = engine.backtest_pnl(&one_sided, Some(0)).unwrap();
+    let favourable = engine.backtest_pnl(&one_sided, Some(1)).unwrap();
+
+    assert!(adverse < 0.0, "Short inventory on the winner should lose, got {}", adverse);
+    assert!(adverse < engine.backtest_pnl(&balanced, Some(0)).unwrap());
+    // The premiums are kept either way; only the 200 shares sold on outcome 0 differ
+    assert!((favourable - adverse - 200.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_backtest_rejects_invalid_outcome() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+
+    assert!(engine.backtest_pnl(&sample_bets(), Some(2)).is_err());
+}
+
+#[test]
+fn test_order_imbalance_tracks_directional_volume() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 3, MarketType::Categorical));
+    let bets = vec![
+        Bet { option_id: 0, amount: 80.0 },
+        Bet { option_id: 1, amount: 15.0 },
+        Bet { option_id: 2, amount: 5.0 },
+    ];
+
+    let imbalance = engine.order_imbalance(&bets).unwrap();
+
+    assert!(imbalance[0] > 0.0);
+    assert!(imbalance[1] < 0.0 && imbalance[2] < 0.0);
+    assert!((imbalance[0] - (80.0 - 100.0 / 3.0) / 100.0).abs() < 1e-12);
+    assert!(imbalance.iter().sum::<f64>().abs() < 1e-12);
+
+    assert_eq!(engine.order_imbalance(&[]).unwrap(), vec![0.0; 3]);
+}
+
+#[test]
+fn test_liquidity_for_impact_cap_grows_with_size_and_tighter_caps() {
+    let base = MarketMakerEngine::liquidity_for_impact_cap(2, 1000.0, 0.05, 0.5).unwrap();
+    let larger_trade = MarketMakerEngine::liquidity_for_impact_cap(2, 2000.0, 0.05, 0.5).unwrap();
+    let tighter_cap = MarketMakerEngine::liquidity_for_impact_cap(2, 1000.0, 0.01, 0.5).unwrap();
+
+    assert!(larger_trade > base);
+    assert!(tighter_cap > base);
+}
+
+#[test]
+fn test_liquidity_for_impact_cap_holds_impact_at_target() {
+    let liquidity = MarketMakerEngine::liquidity_for_impact_cap(2, 1000.0, 0.05, 0.5).unwrap();
+
+    // Shares that take outcome 0 from 0.5 to 0.55, and what they cost at that b
+    let engine = MarketMakerEngine::new(MarketConfig::new(liquidity, 2, MarketType::Binary));
+    let shares = engine.volume_to_reach(&[], 0, 0.55).unwrap();
+    let cost = PredictionMarketEngine::try_new(liquidity, 2, MarketType::Binary).unwrap()
+        .total_collateral(&[Bet { option_id: 0, amount: shares }])
+        .unwrap();
+
+    assert!((cost - 1000.0).abs() < 1e-6, "Capped move should cost the full trade, got {}", cost);
+}
+
+#[test]
+fn test_liquidity_for_impact_cap_rejects_invalid_inputs() {
+    assert!(MarketMakerEngine::liquidity_for_impact_cap(2, 0.0, 0.05, 0.5).is_err());
+    assert!(MarketMakerEngine::liquidity_for_impact_cap(2, 1000.0, 0.5, 0.5).is_err());
+    assert!(MarketMakerEngine::liquidity_for_impact_cap(2, 1000.0, 0.0, 0.5).is_err());
+    assert!(MarketMakerEngine::liquidity_for_impact_cap(1, 1000.0, 0.05, 0.5).is_err());
+}
+
+#[test]
+fn test_effective_probability_brackets_raw_probability() {
+    let config = MarketConfig {
+        taker_fee_bps: 30,
+        ..MarketConfig::new(10.0, 2, MarketType::Binary)
+    };
+    let engine = MarketMakerEngine::new(config.clone());
+    let raw_probability = RiskAssessmentEngine::new(config).assess_risk(&sample_bets()).unwrap().probabilities()[0];
+
+    let buy = engine.effective_probability(&sample_bets(), 0, Side::Buy).unwrap();
+    let sell = engine.effective_probability(&sample_bets(), 0, Side::Sell).unwrap();
+
+    assert!(buy > raw_probability);
+    assert!(sell < raw_probability);
+
+    // Fees widen the gap beyond the quoted spread
+    let strategy = engine.simulate_strategy(&sample_bets()).unwrap();
+    assert!(buy > strategy.ask_prices()[0]);
+    assert!(sell < strategy.bid_prices()[0]);
+
+    assert!(engine.effective_probability(&sample_bets(), 2, Side::Buy).is_err());
+}
+
+#[test]
+fn test_hedge_offsets_long_inventory() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+
+    let hedge = engine.hedge_recommendation(&sample_bets(), &[60.0, 0.0]).unwrap();
+
+    assert_eq!(hedge, vec![(0, -30.0), (1, 30.0)]);
+    assert!(engine.hedge_recommendation(&sample_bets(), &[25.0, 25.0]).unwrap().is_empty());
+}
+
+#[test]
+fn test_hedge_trades_capped_by_liquidity() {
+    let engine = MarketMakerEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+
+    let hedge = engine.hedge_recommendation(&[], &[500.0, 0.0]).unwrap();
+
+    assert_eq!(hedge, vec![(0, -10.0), (1, 10.0)]);
+    assert!(engine.hedge_recommendation(&[], &[1.0]).is_err());
+}
@@ -0,0 +1,63 @@
+use rust_lmsr::{AdaptiveConfig, Bet, MarketConfig, MarketType, PredictionMarketEngine, ProbabilityEngine};
+
+fn probabilities(config: &MarketConfig, bets: &[Bet]) -> Vec<f64> {
+    ProbabilityEngine::new(config.clone()).calculate_probabilities(bets).unwrap().to_f64_vec()
+}
+
+#[test]
+fn test_merged_probabilities_lie_between_inputs() {
+    // Both books lean towards outcome 0; simply adding their quantities at
+    // a single b would push the merged price past both
+    let small_config = MarketConfig::new(20.0, 2, MarketType::Binary);
+    let small_book = vec![Bet { option_id: 0, amount: 30.0 }, Bet { option_id: 1, amount: 10.0 }];
+    let large_config = MarketConfig::new(60.0, 2, MarketType::Binary);
+    let large_book = vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 20.0 },
+        Bet { option_id: 1, amount: 10.0 },
+    ];
+
+    let (config, merged) = PredictionMarketEngine::merge_markets(&large_config, &large_book, &small_config, &small_book)
+        .unwrap();
+    assert_eq!(config.liquidity_param, 80.0);
+    assert_eq!(merged.len(), 2);
+    assert_eq!((merged[0].amount, merged[1].amount), (80.0, 40.0));
+
+    let small = probabilities(&small_config, &small_book)[0];
+    let large = probabilities(&large_config, &large_book)[0];
+    let combined = probabilities(&config, &merged)[0];
+
+    assert!(large < combined && combined < small, "{} not between {} and {}", combined, large, small);
+    // The larger book has three times the depth, so it dominates
+    assert!(combined - large < small - combined);
+}
+
+#[test]
+fn test_merge_pools_per_outcome_liquidity() {
+    let per_outcome = MarketConfig {
+        outcome_liquidity: Some(vec![10.0, 30.0]),
+        ..MarketConfig::new(20.0, 2, MarketType::Binary)
+    };
+    let scalar = MarketConfig::new(40.0, 2, MarketType::Binary);
+
+    let (config, _) = PredictionMarketEngine::merge_markets(&per_outcome, &[], &scalar, &[]).unwrap();
+
+    assert_eq!(config.outcome_liquidity, Some(vec![50.0, 70.0]));
+}
+
+#[test]
+fn test_merge_rejects_misaligned_markets() {
+    let binary = MarketConfig::new(2.0, 2, MarketType::Binary);
+    let categorical = MarketConfig::new(2.0, 3, MarketType::Categorical);
+    let categorical_book = vec![Bet { option_id: 2, amount: 10.0 }];
+
+    assert!(PredictionMarketEngine::merge_markets(&categorical, &categorical_book, &binary, &[]).is_err());
+    assert!(PredictionMarketEngine::merge_markets(&binary, &categorical_book, &binary, &[]).is_err());
+
+    let adaptive = MarketConfig {
+        adaptive_liquidity: Some(AdaptiveConfig { volume_factor: 0.1 }),
+        ..binary.clone()
+    };
+    assert!(PredictionMarketEngine::merge_markets(&adaptive, &[], &binary, &[]).is_err());
+    assert!(PredictionMarketEngine::merge_markets(&adaptive, &[], &adaptive, &[]).is_ok());
+}
@@ -0,0 +1,129 @@
+use rust_lmsr::{Bet, MarketConfig, MarketMakerEngine, MarketType, ProbabilityEngine, RiskAssessmentEngine};
+use rust_decimal::Decimal;
+
+mod common;
+
+// Liquidity of 2 seeds each outcome with 1, so a 1e9 bet leaves the
+// other outcome with a probability of roughly 1e-9.
+fn near_degenerate_market() -> (MarketConfig, Vec<Bet>) {
+    let config = MarketConfig::new(2.0, 2, MarketType::Binary);
+    let bets = vec![Bet { option_id: 0, amount: 1e9 }];
+    (config, bets)
+}
+
+#[test]
+fn test_entropy_finite_for_tiny_probability() {
+    let (config, bets) = near_degenerate_market();
+
+    let profile = RiskAssessmentEngine::new(config).assess_risk(&bets)
+        .expect("Risk assessment should succeed");
+
+    assert!(profile.probabilities()[1] < 1e-8);
+    assert!(profile.entropy().is_finite());
+    assert!(profile.entropy() >= 0.0 && profile.entropy() <= 2f64.ln());
+}
+
+#[test]
+fn test_recommended_liquidity_bounded_for_tiny_probability() {
+    let (config, bets) = near_degenerate_market();
+    let liquidity = config.liquidity_param;
+
+    let strategy = MarketMakerEngine::new(config).simulate_strategy(&bets)
+        .expect("Market making simulation should succeed");
+
+    // Recommendation is b·(1 + entropy), so it can never exceed b·(1 + ln n)
+    assert!(strategy.recommended_liquidity().is_finite());
+    assert!(strategy.recommended_liquidity() >= liquidity);
+    assert!(strategy.recommended_liquidity() <= liquidity * (1.0 + 2f64.ln()));
+}
+
+#[test]
+fn test_equal_outcome_totals_are_deterministic() {
+    let config = MarketConfig::new(9.0, 3, MarketType::Categorical);
+    let bets: Vec<Bet> = (0..3).map(|option_id| Bet { option_id, amount: 20.0 }).collect();
+
+    let first = RiskAssessmentEngine::new(config.clone()).assess_risk(&bets)
+        .expect("Tied outcomes should not panic");
+    let second = RiskAssessmentEngine::new(config.clone()).assess_risk(&bets).unwrap();
+
+    assert_eq!(first, second);
+    assert!((first.concentration() - 1.0 / 3.0).abs() < 1e-12);
+
+    let probabilities = ProbabilityEngine::new(config).calculate_probabilities(&bets)
+        .expect("Tied outcomes should not panic");
+    assert!(probabilities.windows(2).all(|pair| pair[0] == pair[1]));
+}
+
+#[test]
+fn test_probabilities_sum_to_one_for_random_markets() {
+    let mut next = common::lcg(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200 {
+        let num_outcomes = 2 + (next() * 6.0) as usize;
+        let config = MarketConfig::new(1.0 + next() * 1000.0, num_outcomes, MarketType::Categorical);
+        let bets: Vec<Bet> = (0..(next() * 20.0) as usize)
+            .map(|_| Bet {
+                option_id: (next() * num_outcomes as f64) as usize,
+                amount: next() * 10_000.0,
+            })
+            .collect();
+
+        let engine = ProbabilityEngine::new(config);
+        let sum = engine.probabilities_sum(&bets).expect("Sum should be computable");
+        assert!((sum - Decimal::ONE).abs() < Decimal::new(1, 12), "Probabilities summed to {}", sum);
+        assert!(engine.calculate_probabilities(&bets).is_ok());
+    }
+}
+
+#[test]
+fn test_uniform_outcome_liquidity_matches_scalar() {
+    let scalar = MarketConfig::new(60.0, 3, MarketType::Categorical);
+    let uniform = MarketConfig { outcome_liquidity: Some(vec![60.0; 3]), ..scalar.clone() };
+    let bets = vec![Bet { option_id: 0, amount: 25.0 }, Bet { option_id: 2, amount: 7.0 }];
+
+    assert_eq!(
+        ProbabilityEngine::new(scalar).calculate_probabilities(&bets).unwrap(),
+        ProbabilityEngine::new(uniform).calculate_probabilities(&bets).unwrap()
+    );
+}
+
+#[test]
+fn test_lower_outcome_liquidity_makes_price_more_sensitive() {
+    let scalar = MarketConfig::new(60.0, 3, MarketType::Categorical);
+    let thin = MarketConfig { outcome_liquidity: Some(vec![20.0, 60.0, 60.0]), ..scalar.clone() };
+    let bet = [Bet { option_id: 0, amount: 10.0 }];
+
+    let move_for = |config: MarketConfig| {
+        let engine = ProbabilityEngine::new(config);
+        let before = engine.calculate_price(&[], 0).unwrap();
+        engine.calculate_price(&bet, 0).unwrap() - before
+    };
+
+    assert!(move_for(thin) > move_for(scalar));
+
+    let wrong_length = MarketConfig { outcome_liquidity: Some(vec![20.0, 60.0]), ..MarketConfig::new(60.0, 3, MarketType::Categorical) };
+    assert!(ProbabilityEngine::new(wrong_length).calculate_probabilities(&bet).is_err());
+}
+
+#[test]
+fn test_bets_move_probabilities_monotonically() {
+    let mut next = common::lcg(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200 {
+        let num_outcomes = 2 + (next() * 5.0) as usize;
+        let engine = ProbabilityEngine::new(MarketConfig::new(100.0 + next() * 500.0, num_outcomes, MarketType::Categorical));
+        let mut random_bet = |scale: f64| Bet {
+            option_id: (next() * num_outcomes as f64) as usize,
+            amount: 1.0 + next() * scale,
+        };
+        let bets: Vec<Bet> = (0..10).map(|_| random_bet(50.0)).collect();
+        let bet = random_bet(50.0);
+
+        assert!(
+            engine.verify_monotonicity(&bets, bet.clone()).unwrap(),
+            "Bet {:?} broke monotonicity after {:?}",
+            bet,
+            bets
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use rust_lmsr::{LimitOrder, LimitOrderBook, Side};
+
+fn buy_limit(outcome: usize, target_probability: f64) -> LimitOrder {
+    LimitOrder { outcome, side: Side::Buy, target_probability, size: 10.0 }
+}
+
+#[test]
+fn test_buy_limit_fills_when_probability_drops_to_target() {
+    let mut book = LimitOrderBook::new();
+    let id = book.place(buy_limit(0, 0.4)).unwrap();
+
+    let filled = book.match_against(&[0.4, 0.6]);
+
+    assert_eq!(filled.len(), 1);
+    assert_eq!(filled[0].order_id, id);
+    assert_eq!(filled[0].fill_probability, 0.4);
+}
+
+#[test]
+fn test_buy_limit_rests_above_target() {
+    let mut book = LimitOrderBook::new();
+    book.place(buy_limit(0, 0.4)).unwrap();
+
+    assert!(book.match_against(&[0.55, 0.45]).is_empty());
+    assert_eq!(book.len(), 1, "Unfilled orders should keep resting");
+}
+
+#[test]
+fn test_sell_limit_fills_at_or_above_target() {
+    let mut book = LimitOrderBook::new();
+    book.place(LimitOrder { outcome: 1, side: Side::Sell, target_probability: 0.7, size: 5.0 }).unwrap();
+
+    assert!(book.match_against(&[0.4, 0.6]).is_empty());
+    assert_eq!(book.match_against(&[0.25, 0.75]).len(), 1);
+}
+
+#[test]
+fn test_invalid_target_is_rejected() {
+    let mut book = LimitOrderBook::new();
+
+    assert!(book.place(buy_limit(0, 1.0)).is_err());
+    assert!(book.is_empty());
+}
@@ -0,0 +1,51 @@
+use rust_decimal::prelude::*;
+use rust_lmsr::{outcome_totals, Bet, MarketConfig, MarketMakerEngine, MarketType, ProbabilityEngine, RiskAssessmentEngine};
+
+fn market() -> (MarketConfig, Vec<Bet>) {
+    let config = MarketConfig::new(30.0, 3, MarketType::Categorical);
+    let bets = vec![
+        Bet { option_id: 0, amount: 40.0 },
+        Bet { option_id: 2, amount: 15.5 },
+        Bet { option_id: 0, amount: 4.5 },
+    ];
+    (config, bets)
+}
+
+#[test]
+fn test_outcome_totals_seed_and_accumulate() {
+    let (config, bets) = market();
+
+    let totals = outcome_totals(&config, &bets).unwrap();
+
+    assert_eq!(totals.to_vec(), vec![Decimal::new(545, 1), Decimal::from(10), Decimal::new(255, 1)]);
+    assert!(outcome_totals(&config, &[Bet { option_id: 3, amount: 1.0 }]).is_err());
+}
+
+#[test]
+fn test_engines_derive_state_from_shared_totals() {
+    let (config, bets) = market();
+    let totals = outcome_totals(&config, &bets).unwrap();
+
+    // LMSR over the same totals, less the b/n seed
+    let seed = config.liquidity_param / config.num_outcomes as f64;
+    let exps: Vec<f64> = totals.iter()
+        .map(|t| ((t.to_f64().unwrap() - seed) / config.liquidity_param).exp())
+        .collect();
+    let sum: f64 = exps.iter().sum();
+    let expected: Vec<f64> = exps.iter().map(|e| e / sum).collect();
+
+    let probabilities = ProbabilityEngine::new(config.clone()).calculate_probabilities(&bets).unwrap();
+    for (p, e) in probabilities.iter().zip(&expected) {
+        assert!((p.to_f64().unwrap() - e).abs() < 1e-12);
+    }
+
+    // Risk and maker quotes are measured on exactly those prices
+    let risk = RiskAssessmentEngine::new(config.clone()).assess_risk(&bets).unwrap();
+    assert_eq!(risk.probabilities(), probabilities.to_f64_vec());
+
+    // Maker quotes bid at p·(1 - 0.05)
+    let strategy = MarketMakerEngine::new(config).simulate_strategy(&bets).unwrap();
+    for (bid, p) in strategy.bid_prices().iter().zip(&expected) {
+        assert!((bid - p * 0.95).abs() < 1e-12);
+    }
+}
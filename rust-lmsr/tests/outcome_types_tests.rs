@@ -0,0 +1,40 @@
+use rust_decimal::Decimal;
+use rust_lmsr::{Bet, MarketError, OutcomeTotals, Probabilities};
+
+#[test]
+fn test_outcome_totals_reject_non_positive_entries() {
+    assert!(OutcomeTotals::new(vec![Decimal::from(5), Decimal::from(3)]).is_ok());
+    assert!(OutcomeTotals::new(vec![Decimal::from(5), Decimal::ZERO]).is_err());
+    assert!(OutcomeTotals::new(vec![Decimal::from(-1), Decimal::from(3)]).is_err());
+    assert!(OutcomeTotals::new(Vec::new()).is_err());
+}
+
+#[test]
+fn test_outcome_totals_stay_positive_as_bets_arrive() {
+    let mut totals = OutcomeTotals::new(vec![Decimal::from(5), Decimal::from(3)]).unwrap();
+
+    totals.add_bet(&Bet { option_id: 1, amount: 2.0 }).unwrap();
+    assert_eq!(totals.to_vec(), vec![Decimal::from(5), Decimal::from(5)]);
+
+    assert!(matches!(totals.add_bet(&Bet { option_id: 2, amount: 1.0 }), Err(MarketError::InvalidOutcomeIndex(2))));
+    assert!(totals.add_bet(&Bet { option_id: 0, amount: -5.0 }).is_err());
+    assert_eq!(totals.volume(), Decimal::from(10));
+}
+
+#[test]
+fn test_probabilities_reject_invalid_distributions() {
+    let half = Decimal::new(5, 1);
+    assert!(Probabilities::new(vec![half, half]).is_ok());
+    assert!(Probabilities::new(vec![half, Decimal::new(6, 1)]).is_err());
+    assert!(Probabilities::new(vec![Decimal::new(15, 1), Decimal::new(-5, 1)]).is_err());
+    assert!(Probabilities::new(Vec::new()).is_err());
+}
+
+#[test]
+fn test_proportional_probabilities_follow_volume() {
+    let totals = OutcomeTotals::new(vec![Decimal::from(30), Decimal::from(10)]).unwrap();
+
+    let probabilities = Probabilities::proportional(&totals).unwrap();
+
+    assert_eq!(probabilities.to_vec(), vec![Decimal::new(75, 2), Decimal::new(25, 2)]);
+}
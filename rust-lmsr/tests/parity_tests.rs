@@ -0,0 +1,87 @@
+use rust_decimal::prelude::*;
+use rust_lmsr::{
+    Bet, IncrementalMarket, MarketConfig, MarketMakerEngine, MarketType, PredictionMarketEngine, ProbabilityEngine,
+    RiskAssessmentEngine,
+};
+
+// Golden cases shared with wasm-lmsr's parity tests
+include!("fixtures/parity_cases.rs");
+
+// Guards against the LMSR entry points drifting apart
+const TOLERANCE: f64 = 1e-9;
+
+// Relative half-spread the maker quotes around the fair price
+const HALF_SPREAD: f64 = 0.05;
+
+// Splits each outcome's volume into two bets, interleaved across outcomes
+fn bets_for(volumes: &[f64]) -> Vec<Bet> {
+    let mut bets = Vec::new();
+    for half in [0.25, 0.75] {
+        for (option_id, volume) in volumes.iter().enumerate() {
+            bets.push(Bet { option_id, amount: volume * half });
+        }
+    }
+    bets
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], entry_point: &str, case: usize) {
+    assert_eq!(actual.len(), expected.len(), "{} returned the wrong outcome count in case {}", entry_point, case);
+    for (outcome, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (a - e).abs() < TOLERANCE,
+            "{} drifted in case {} outcome {}: {} vs golden {}", entry_point, case, outcome, a, e
+        );
+    }
+}
+
+#[test]
+fn test_fixture_spans_required_cases() {
+    assert!(PARITY_CASES.len() >= 20);
+    for outcomes in 2..=8 {
+        assert!(PARITY_CASES.iter().any(|case| case.volumes.len() == outcomes), "No case with {} outcomes", outcomes);
+    }
+}
+
+#[test]
+fn test_probability_entry_points_match_fixture() {
+    for (case, fixture) in PARITY_CASES.iter().enumerate() {
+        let expected = fixture.probabilities;
+        let config = MarketConfig::new(fixture.liquidity, fixture.volumes.len(), MarketType::Categorical);
+        let bets = bets_for(fixture.volumes);
+
+        let engine = ProbabilityEngine::new(config.clone());
+        assert_close(&engine.calculate_probabilities(&bets).unwrap().to_f64_vec(), expected, "calculate_probabilities", case);
+
+        let prices: Vec<f64> = (0..fixture.volumes.len())
+            .map(|outcome| engine.calculate_price(&bets, outcome).unwrap().to_f64().unwrap())
+            .collect();
+        assert_close(&prices, expected, "calculate_price", case);
+
+        let mut market = IncrementalMarket::new(config.clone()).unwrap();
+        for bet in &bets {
+            market.apply_bet(bet).unwrap();
+        }
+        assert_close(&market.probabilities().unwrap(), expected, "IncrementalMarket", case);
+
+        let analysis = PredictionMarketEngine::try_from_config(config).unwrap().analyze(&bets).unwrap();
+        assert_close(&analysis.probabilities, expected, "analyze", case);
+    }
+}
+
+#[test]
+fn test_maker_and_risk_engines_match_fixture() {
+    for (case, fixture) in PARITY_CASES.iter().enumerate() {
+        let config = MarketConfig::new(fixture.liquidity, fixture.volumes.len(), MarketType::Categorical);
+        let bets = bets_for(fixture.volumes);
+
+        let strategy = MarketMakerEngine::new(config.clone()).simulate_strategy(&bets).unwrap();
+        let bids: Vec<f64> = fixture.probabilities.iter().map(|p| p * (1.0 - HALF_SPREAD)).collect();
+        let asks: Vec<f64> = fixture.probabilities.iter().map(|p| p * (1.0 + HALF_SPREAD)).collect();
+        assert_close(&strategy.bid_prices(), &bids, "simulate_strategy bids", case);
+        assert_close(&strategy.ask_prices(), &asks, "simulate_strategy asks", case);
+
+        let risk = RiskAssessmentEngine::new(config).assess_risk(&bets).unwrap();
+        assert_close(&risk.probabilities(), fixture.probabilities, "assess_risk", case);
+        assert_close(&[risk.entropy()], &[fixture.entropy], "assess_risk entropy", case);
+    }
+}
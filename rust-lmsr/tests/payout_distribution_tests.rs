@@ -0,0 +1,27 @@
+use rust_lmsr::{Bet, MarketType, PredictionMarketEngine};
+
+#[test]
+fn test_hedged_positions_pay_out_on_every_outcome() {
+    let engine = PredictionMarketEngine::try_new(100.0, 2, MarketType::Binary).unwrap();
+    let bets = vec![
+        Bet { option_id: 0, amount: 60.0 },
+        Bet { option_id: 1, amount: 20.0 },
+    ];
+
+    let distribution = engine.payout_distribution(&bets, &[(0, 30.0), (1, 45.0), (0, 5.0)]).unwrap();
+
+    assert_eq!(distribution.len(), 2);
+    assert_eq!(distribution[0].0, 0);
+    assert_eq!(distribution[0].2, 35.0);
+    assert_eq!(distribution[1].2, 45.0);
+    assert!(distribution.iter().all(|&(_, _, payout)| payout > 0.0));
+    assert!((distribution.iter().map(|&(_, p, _)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(distribution[0].1 > distribution[1].1);
+}
+
+#[test]
+fn test_unknown_position_outcome_rejected() {
+    let engine = PredictionMarketEngine::try_new(100.0, 2, MarketType::Binary).unwrap();
+
+    assert!(engine.payout_distribution(&[], &[(2, 1.0)]).is_err());
+}
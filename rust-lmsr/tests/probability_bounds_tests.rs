@@ -0,0 +1,45 @@
+use rust_lmsr::ProbabilityEngine;
+
+#[test]
+fn test_bounds_for_single_unknown_outcome() {
+    let bounds = ProbabilityEngine::arbitrage_free_bounds(&[(0, 0.5), (2, 0.2)], 3)
+        .expect("Consistent prices should produce bounds");
+
+    assert_eq!(bounds.len(), 1);
+    assert!((bounds[0].0 - 0.3).abs() < 1e-12);
+    assert!((bounds[0].1 - 0.3).abs() < 1e-12);
+}
+
+#[test]
+fn test_bounds_for_multiple_unknown_outcomes() {
+    let bounds = ProbabilityEngine::arbitrage_free_bounds(&[(1, 0.4)], 3)
+        .expect("Consistent prices should produce bounds");
+
+    assert_eq!(bounds.len(), 2);
+    for (min, max) in bounds {
+        assert_eq!(min, 0.0);
+        assert!((max - 0.6).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_known_prices_above_one_rejected() {
+    assert!(ProbabilityEngine::arbitrage_free_bounds(&[(0, 0.7), (1, 0.5)], 3).is_err());
+    assert!(ProbabilityEngine::arbitrage_free_bounds(&[(0, 0.7), (3, 0.1)], 3).is_err());
+}
+
+#[test]
+fn test_break_even_probability() {
+    use rust_lmsr::{MarketConfig, MarketType};
+
+    let engine = ProbabilityEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+
+    assert_eq!(engine.break_even_probability(0.4, 0).unwrap(), 0.4);
+
+    let with_fee = engine.break_even_probability(0.4, 250).unwrap();
+    assert!(with_fee > 0.4);
+    assert!((with_fee - 0.41).abs() < 1e-12);
+
+    assert!(engine.break_even_probability(0.99, 200).is_err());
+    assert!(engine.break_even_probability(1.5, 0).is_err());
+}
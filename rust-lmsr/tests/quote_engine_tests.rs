@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use rust_lmsr::{Bet, MarketConfig, MarketMakerEngine, MarketType, QuoteEngine};
+
+fn market() -> (MarketConfig, Vec<Bet>) {
+    let config = MarketConfig::new(100.0, 2, MarketType::Binary);
+    let bets = vec![Bet { option_id: 0, amount: 30.0 }, Bet { option_id: 1, amount: 30.0 }];
+    (config, bets)
+}
+
+#[test]
+fn test_significant_inventory_change_triggers_refresh() {
+    let (config, bets) = market();
+    let mut engine = QuoteEngine::new(config);
+    let tick = Duration::from_secs(1);
+
+    let first = engine.refresh(&bets, &[0.0, 0.0], tick, 0.0).unwrap();
+    assert!(first.refreshed, "The first quotes are always published");
+
+    let trivial = engine.refresh(&bets, &[0.5, -0.5], tick, 0.0).unwrap();
+    assert!(!trivial.refreshed);
+    assert_eq!(trivial.strategy, first.strategy);
+
+    // Long outcome 0 by half of b: its mid drops, outcome 1's rises
+    let significant = engine.refresh(&bets, &[50.0, -50.0], tick, 0.0).unwrap();
+    assert!(significant.refreshed);
+    let mid = |i: usize| (significant.strategy.bid_prices()[i] + significant.strategy.ask_prices()[i]) / 2.0;
+    assert!(mid(0) < 0.5 && mid(1) > 0.5);
+    assert_eq!(engine.standing(), Some(&significant.strategy));
+}
+
+#[test]
+fn test_old_quotes_refresh_even_when_unchanged() {
+    let (config, bets) = market();
+    let mut engine = QuoteEngine::new(config).with_max_quote_age(Duration::from_secs(30));
+
+    engine.refresh(&bets, &[0.0, 0.0], Duration::ZERO, 0.0).unwrap();
+    assert!(!engine.refresh(&bets, &[0.0, 0.0], Duration::from_secs(29), 0.0).unwrap().refreshed);
+    assert!(engine.refresh(&bets, &[0.0, 0.0], Duration::from_secs(30), 0.0).unwrap().refreshed);
+}
+
+#[test]
+fn test_flat_inventory_quotes_match_volatility_strategy() {
+    let (config, bets) = market();
+    let maker = MarketMakerEngine::new(config);
+
+    assert_eq!(
+        maker.simulate_strategy_with_inventory(&bets, &[7.0, 7.0], 0.4).unwrap(),
+        maker.simulate_strategy_with_volatility(&bets, 0.4).unwrap()
+    );
+    assert!(maker.simulate_strategy_with_inventory(&bets, &[1.0], 0.0).is_err());
+}
@@ -0,0 +1,33 @@
+use rust_lmsr::{MarketConfig, MarketType, ProbabilityEngine};
+
+fn scalar_engine() -> ProbabilityEngine {
+    ProbabilityEngine::new(MarketConfig::new(100.0, 100, MarketType::Scalar))
+}
+
+#[test]
+fn test_rebucket_preserves_mass() {
+    // Unnormalized triangular shape over 100 fine buckets
+    let weights: Vec<f64> = (1..=100).map(|i| i as f64).collect();
+    let total: f64 = weights.iter().sum();
+    let fine: Vec<f64> = weights.iter().map(|w| w / total).collect();
+
+    let coarse = scalar_engine().rebucket(&fine, 10)
+        .expect("100 buckets should divide into 10");
+
+    assert_eq!(coarse.len(), 10);
+    assert!((coarse.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    for (i, p) in coarse.iter().enumerate() {
+        let expected: f64 = fine[i * 10..(i + 1) * 10].iter().sum();
+        assert!((p - expected).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn test_rebucket_rejects_uneven_or_larger_targets() {
+    let fine = vec![0.01; 100];
+    let engine = scalar_engine();
+
+    assert!(engine.rebucket(&fine, 7).is_err());
+    assert!(engine.rebucket(&fine, 200).is_err());
+    assert!(engine.rebucket(&fine, 0).is_err());
+}
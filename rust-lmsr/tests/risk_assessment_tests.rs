@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use rust_lmsr::{Bet, MarketConfig, MarketError, MarketType, RiskAssessmentEngine, TimedBet};
+
+#[test]
+fn test_batch_reports_per_market_results() {
+    let binary = MarketConfig::new(100.0, 2, MarketType::Binary);
+    let categorical = MarketConfig::new(50.0, 3, MarketType::Categorical);
+    let bets = vec![Bet { option_id: 0, amount: 40.0 }, Bet { option_id: 1, amount: 10.0 }];
+
+    let results = RiskAssessmentEngine::assess_risk_batch(vec![
+        (binary.clone(), bets.clone()),
+        (categorical.clone(), vec![Bet { option_id: 7, amount: 5.0 }]),
+        (MarketConfig::new(0.0, 2, MarketType::Binary), bets.clone()),
+        (categorical.clone(), bets.clone()),
+    ]);
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &RiskAssessmentEngine::new(binary).assess_risk(&bets).unwrap());
+    assert!(matches!(results[1], Err(MarketError::InvalidOutcomeIndex(7))));
+    assert!(matches!(results[2], Err(MarketError::InvalidLiquidity(_))));
+    assert_eq!(results[3].as_ref().unwrap(), &RiskAssessmentEngine::new(categorical).assess_risk(&bets).unwrap());
+}
+
+#[test]
+fn test_jumpy_path_more_volatile_than_smooth_path() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+
+    // The same back-and-forth flow, delivered as many small bets or a few large ones
+    let smooth: Vec<Bet> = (0..40).map(|i| Bet { option_id: i % 2, amount: 5.0 }).collect();
+    let jumpy: Vec<Bet> = (0..4).map(|i| Bet { option_id: i % 2, amount: 100.0 }).collect();
+
+    let smooth_vol = engine.path_volatility(&smooth).expect("Smooth path should be scored");
+    let jumpy_vol = engine.path_volatility(&jumpy).expect("Jumpy path should be scored");
+
+    assert!(smooth_vol > 0.0);
+    assert!(jumpy_vol > smooth_vol * 5.0, "Jumpy {} vs smooth {}", jumpy_vol, smooth_vol);
+
+    // A steady drift moves the market every bet but barely varies
+    let drift: Vec<Bet> = (0..40).map(|_| Bet { option_id: 0, amount: 5.0 }).collect();
+    let drift_vol = engine.path_volatility(&drift).unwrap();
+    assert!(drift_vol < smooth_vol / 5.0, "Drift {} vs smooth {}", drift_vol, smooth_vol);
+
+    assert!(engine.path_volatility(&[]).is_err());
+    assert!(engine.path_volatility(&smooth[..1]).is_err());
+}
+
+#[test]
+fn test_information_gain_reflects_how_far_a_bet_moves_the_market() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    let before = vec![Bet { option_id: 0, amount: 900.0 }, Bet { option_id: 1, amount: 50.0 }];
+
+    let surprise = engine.information_gain(&before, Bet { option_id: 1, amount: 2000.0 }).unwrap();
+    let negligible = engine.information_gain(&before, Bet { option_id: 1, amount: 0.01 }).unwrap();
+
+    assert!(surprise > 0.5, "Large contrarian bet should be informative, got {}", surprise);
+    assert!(negligible < 1e-8, "Tiny bet should carry almost no information, got {}", negligible);
+    assert!(engine.information_gain(&before, Bet { option_id: 2, amount: 1.0 }).is_err());
+}
+
+fn minute_by_minute(amounts: &[f64]) -> Vec<TimedBet> {
+    let start = Utc::now() - chrono::Duration::hours(1);
+    amounts.iter()
+        .enumerate()
+        .map(|(minute, &amount)| TimedBet {
+            bet: Bet { option_id: minute % 2, amount },
+            placed_at: start + chrono::Duration::minutes(minute as i64),
+        })
+        .collect()
+}
+
+#[test]
+fn test_late_surge_shows_high_bet_acceleration() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    let window = Duration::from_secs(5 * 60);
+
+    let steady = engine.bet_velocity(&minute_by_minute(&[10.0; 20]), window).unwrap();
+
+    let mut surging_amounts = vec![10.0; 15];
+    surging_amounts.extend([100.0; 5]);
+    let surging = engine.bet_velocity(&minute_by_minute(&surging_amounts), window).unwrap();
+
+    assert!(steady.acceleration.abs() < 1e-12);
+    assert!((steady.rate - 50.0 / 300.0).abs() < 1e-12);
+    assert!(surging.rate > steady.rate);
+    assert!(surging.acceleration > 100.0 * steady.acceleration.abs().max(1e-6));
+
+    // The same velocity is surfaced on the risk profile
+    let timed = minute_by_minute(&surging_amounts);
+    let bets: Vec<Bet> = timed.iter().map(|timed| timed.bet.clone()).collect();
+    let profile = engine.assess_risk_timed(&timed, window).unwrap();
+    assert_eq!(profile.velocity(), Some(surging));
+    assert_eq!(profile.bet_acceleration(), Some(surging.acceleration));
+    assert_eq!(profile.probabilities(), engine.assess_risk(&bets).unwrap().probabilities());
+    assert_eq!(engine.assess_risk(&[]).unwrap().velocity(), None);
+}
+
+#[test]
+fn test_bet_velocity_rejects_bad_input() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    let window = Duration::from_secs(60);
+
+    assert!(engine.bet_velocity(&[], window).is_err());
+    assert!(engine.bet_velocity(&minute_by_minute(&[1.0]), Duration::ZERO).is_err());
+
+    let mut out_of_order = minute_by_minute(&[1.0, 2.0]);
+    out_of_order.reverse();
+    assert!(engine.bet_velocity(&out_of_order, window).is_err());
+
+    assert!(engine.bet_velocity(&minute_by_minute(&[1.0, f64::NAN]), window).is_err());
+    assert!(engine.bet_velocity(&minute_by_minute(&[1.0, -5.0]), window).is_err());
+    assert!(engine.assess_risk_timed(&minute_by_minute(&[1.0, -5.0]), window).is_err());
+}
+
+#[test]
+fn test_portfolio_var_diversifies_independent_positions() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    // An untraded 10-outcome market is uniform; the position loses 10·i if outcome i wins
+    let position = || {
+        let profits: Vec<f64> = (0..10).map(|i| -10.0 * i as f64).collect();
+        (MarketConfig::new(100.0, 10, MarketType::Categorical), Vec::new(), profits)
+    };
+
+    let single = engine.portfolio_var(&[position()], 0.95).unwrap();
+    let combined = engine.portfolio_var(&[position(), position()], 0.95).unwrap();
+
+    assert_eq!(single, 90.0);
+    assert!(combined < 2.0 * single, "Combined VaR {} should show diversification", combined);
+    assert!(combined > single);
+}
+
+#[test]
+fn test_portfolio_var_scales_to_many_positions() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    // 40 ten-outcome positions have 10^40 joint outcomes
+    let profits: Vec<f64> = (0..10).map(|i| -10.0 * i as f64).collect();
+    let positions = vec![(MarketConfig::new(100.0, 10, MarketType::Categorical), Vec::new(), profits); 40];
+
+    let var = engine.portfolio_var(&positions, 0.95).unwrap();
+
+    // Exact distribution of the total loss, 10 × a sum of 40 uniform digits
+    let mut exact = vec![1.0];
+    for _ in 0..40 {
+        let mut next = vec![0.0; exact.len() + 9];
+        for (total, p) in exact.iter().enumerate() {
+            for digit in 0..10 {
+                next[total + digit] += p / 10.0;
+            }
+        }
+        exact = next;
+    }
+    let mut cumulative = 0.0;
+    let quantile = exact.iter().position(|p| {
+        cumulative += p;
+        cumulative >= 0.95 - 1e-9
+    }).unwrap();
+
+    assert!((var - 10.0 * quantile as f64).abs() < 1e-6, "{} vs {}", var, 10.0 * quantile as f64);
+}
+
+#[test]
+fn test_portfolio_var_rejects_bad_input() {
+    let engine = RiskAssessmentEngine::new(MarketConfig::new(100.0, 2, MarketType::Binary));
+    let position = (MarketConfig::new(100.0, 2, MarketType::Binary), Vec::new(), vec![10.0, -10.0]);
+
+    assert!(engine.portfolio_var(&[], 0.95).is_err());
+    assert!(engine.portfolio_var(std::slice::from_ref(&position), 1.0).is_err());
+    assert!(engine.portfolio_var(&[(position.0, position.1, vec![1.0])], 0.95).is_err());
+}
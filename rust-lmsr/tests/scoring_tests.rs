@@ -0,0 +1,50 @@
+use rust_lmsr::{brier_decomposition, log_loss};
+
+#[test]
+fn test_confident_correct_prediction_scores_near_zero() {
+    let loss = log_loss(&[0.99, 0.01], 0).expect("Scoring should succeed");
+    assert!(loss < 0.02, "Expected a near-zero loss, got {}", loss);
+}
+
+#[test]
+fn test_confident_wrong_prediction_scores_high() {
+    let loss = log_loss(&[0.99, 0.01], 1).expect("Scoring should succeed");
+    assert!(loss > 4.0, "Expected a high loss, got {}", loss);
+
+    // A zero probability is floored rather than scoring infinity
+    let certain_miss = log_loss(&[1.0, 0.0], 1).expect("Scoring should succeed");
+    assert!(certain_miss.is_finite() && certain_miss > loss);
+}
+
+#[test]
+fn test_unknown_outcome_rejected() {
+    assert!(log_loss(&[0.5, 0.5], 2).is_err());
+}
+
+#[test]
+fn test_brier_decomposition_components_sum_to_score() {
+    let observations = vec![
+        (vec![0.8, 0.2], 0),
+        (vec![0.8, 0.2], 0),
+        (vec![0.8, 0.2], 1),
+        (vec![0.3, 0.7], 1),
+    ];
+
+    let decomposition = brier_decomposition(&observations).expect("Decomposition should succeed");
+
+    // (2·0.08 + 1.28 + 0.18) / 4
+    assert!((decomposition.brier - 0.405).abs() < 1e-12);
+    assert!((decomposition.reliability - 43.0 / 600.0).abs() < 1e-12);
+    assert!((decomposition.resolution - 1.0 / 6.0).abs() < 1e-12);
+    assert!((decomposition.uncertainty - 0.5).abs() < 1e-12);
+
+    let recombined = decomposition.reliability - decomposition.resolution + decomposition.uncertainty;
+    assert!((recombined - decomposition.brier).abs() < 1e-12);
+}
+
+#[test]
+fn test_brier_decomposition_rejects_bad_observations() {
+    assert!(brier_decomposition(&[]).is_err());
+    assert!(brier_decomposition(&[(vec![0.5, 0.5], 2)]).is_err());
+    assert!(brier_decomposition(&[(vec![0.5, 0.5], 0), (vec![1.0], 0)]).is_err());
+}
@@ -0,0 +1,201 @@
+use rust_lmsr::{
+    Bet, ManualOracle, MarketConfig, MarketError, MarketType, OracleError, OutcomeOracle, PayoutModel,
+    PredictionMarketEngine, SettlementEngine, SettlementLedger, SettlementOutcome,
+};
+
+fn binary_config() -> MarketConfig {
+    MarketConfig::new(100.0, 2, MarketType::Binary)
+}
+
+fn sample_bets() -> Vec<Bet> {
+    vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 30.0 },
+        Bet { option_id: 0, amount: 20.0 },
+    ]
+}
+
+#[test]
+fn test_void_refunds_original_stakes() {
+    let engine = SettlementEngine::new(binary_config());
+    let bets = sample_bets();
+    let market = PredictionMarketEngine::try_new(100.0, 2, MarketType::Binary).unwrap();
+
+    let payouts = engine.resolve_market(&bets, &SettlementOutcome::Void)
+        .expect("Void settlement should succeed");
+
+    // Each bet gets back exactly what its shares cost when it was placed
+    for (i, payout) in payouts.iter().enumerate() {
+        let paid = market.total_collateral(&bets[..=i]).unwrap() - market.total_collateral(&bets[..i]).unwrap();
+        assert!((payout - paid).abs() < 1e-9, "Void must refund the original stake");
+    }
+    let refunded: f64 = payouts.iter().sum();
+    assert!((refunded - engine.collected_collateral(&bets).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_two_way_split_conserves_collateral() {
+    let config = binary_config();
+    let engine = SettlementEngine::new(config.clone());
+    let bets = sample_bets();
+
+    let split = engine.resolve_market(&bets, &SettlementOutcome::Split(vec![(0, 0.25), (1, 0.75)]))
+        .expect("Split settlement should succeed");
+    let zero_wins: f64 = engine.resolve_market(&bets, &SettlementOutcome::Winner(0)).unwrap().iter().sum();
+    let one_wins: f64 = engine.resolve_market(&bets, &SettlementOutcome::Winner(1)).unwrap().iter().sum();
+
+    let split_total: f64 = split.iter().sum();
+    assert!((split_total - (0.25 * zero_wins + 0.75 * one_wins)).abs() < 1e-9);
+
+    // The maker can never pay out more than it collected plus its b·ln(n) subsidy
+    let max_subsidy = config.liquidity_param * (config.num_outcomes as f64).ln();
+    assert!(split_total <= engine.collected_collateral(&bets).unwrap() + max_subsidy);
+    assert!(split.iter().all(|&p| p > 0.0), "Both sides should be paid in a split");
+}
+
+#[test]
+fn test_split_weights_must_sum_to_one() {
+    let engine = SettlementEngine::new(binary_config());
+
+    let result = engine.resolve_market(&sample_bets(), &SettlementOutcome::Split(vec![(0, 0.5), (1, 0.4)]));
+    assert!(result.is_err(), "Weights not summing to 1 should be rejected");
+}
+
+#[test]
+fn test_parimutuel_pool_redistributed_net_of_fees() {
+    let config = MarketConfig {
+        payout_model: PayoutModel::Parimutuel,
+        fee_bps: 200,
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    };
+    let engine = SettlementEngine::new(config);
+    let bets = vec![
+        Bet { option_id: 0, amount: 60.0 },
+        Bet { option_id: 0, amount: 20.0 },
+        Bet { option_id: 1, amount: 120.0 },
+    ];
+    let outcome = SettlementOutcome::Winner(0);
+
+    let payouts = engine.resolve_market(&bets, &outcome)
+        .expect("Parimutuel settlement should succeed");
+
+    // Pool of 200 less a 2% fee leaves 196 for the 80 staked on the winner
+    assert!((payouts[0] - 60.0 * 196.0 / 80.0).abs() < 1e-9);
+    assert!((payouts[1] - 20.0 * 196.0 / 80.0).abs() < 1e-9);
+    assert_eq!(payouts[2], 0.0);
+
+    let fee = engine.settlement_fee(&bets, &outcome);
+    let paid: f64 = payouts.iter().sum();
+    assert!((paid + fee - engine.collected_collateral(&bets).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_parimutuel_refunds_pool_when_no_stake_on_winner() {
+    let config = MarketConfig {
+        payout_model: PayoutModel::Parimutuel,
+        fee_bps: 200,
+        ..MarketConfig::new(100.0, 2, MarketType::Binary)
+    };
+    let engine = SettlementEngine::new(config);
+    // The only bet on the winner stakes nothing
+    let bets = vec![
+        Bet { option_id: 0, amount: 60.0 },
+        Bet { option_id: 1, amount: 0.0 },
+        Bet { option_id: 0, amount: 40.0 },
+    ];
+    let outcome = SettlementOutcome::Winner(1);
+
+    let payouts = engine.resolve_market(&bets, &outcome).unwrap();
+
+    // Every stake comes back less the 2% fee
+    assert!(payouts.iter().all(|payout| payout.is_finite()));
+    assert!((payouts[0] - 60.0 * 0.98).abs() < 1e-9);
+    assert_eq!(payouts[1], 0.0);
+    assert!((payouts[2] - 40.0 * 0.98).abs() < 1e-9);
+
+    let paid: f64 = payouts.iter().sum();
+    assert!((paid + engine.settlement_fee(&bets, &outcome) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_resume_pays_only_pending_positions() {
+    let engine = SettlementEngine::new(binary_config());
+    let bets = vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 0, amount: 20.0 },
+        Bet { option_id: 0, amount: 10.0 },
+        Bet { option_id: 0, amount: 5.0 },
+    ];
+    let mut ledger = engine.prepare_settlement("market-1", &bets, &SettlementOutcome::Winner(0)).unwrap();
+    let expected: Vec<f64> = ledger.payouts.iter().map(|payout| payout.amount).collect();
+
+    // The process dies while paying the third position
+    let mut paid = Vec::new();
+    let crashed = engine.resume_settlement(&mut ledger, |_, payout| {
+        if payout.position == 2 {
+            return Err(MarketError::InvalidSettlement("crash".to_string()));
+        }
+        paid.push((payout.position, payout.amount));
+        Ok(())
+    });
+    assert!(crashed.is_err());
+    assert_eq!(paid.len(), 2);
+
+    // Restored from the persisted ledger, resume pays exactly the remainder
+    let mut ledger: SettlementLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+    let resumed = engine.resume_settlement(&mut ledger, |market_id, payout| {
+        assert_eq!(market_id, "market-1");
+        paid.push((payout.position, payout.amount));
+        Ok(())
+    }).unwrap();
+    assert_eq!(resumed, 2);
+    assert!(ledger.is_complete());
+    assert_eq!(paid, expected.iter().copied().enumerate().collect::<Vec<_>>());
+
+    // Re-running a completed settlement is a no-op
+    let rerun = engine.resume_settlement(&mut ledger, |_, _| panic!("Nothing should be paid twice")).unwrap();
+    assert_eq!(rerun, 0);
+}
+
+// Reports a fixed winner for one market and nothing for any other
+struct FakeOracle;
+
+impl OutcomeOracle for FakeOracle {
+    fn resolve(&self, market_id: &str) -> Result<SettlementOutcome, OracleError> {
+        match market_id {
+            "market-1" => Ok(SettlementOutcome::Winner(1)),
+            _ => Err(OracleError::Unavailable("feed down".to_string())),
+        }
+    }
+}
+
+#[test]
+fn test_oracle_drives_market_to_settlement() {
+    let engine = SettlementEngine::new(binary_config()).with_oracle(Box::new(FakeOracle));
+    let bets = sample_bets();
+
+    let mut ledger = engine.auto_resolve("market-1", &bets).unwrap();
+    assert_eq!(ledger.outcome, SettlementOutcome::Winner(1));
+    assert_eq!(
+        ledger.payouts.iter().map(|payout| payout.amount).collect::<Vec<_>>(),
+        engine.resolve_market(&bets, &SettlementOutcome::Winner(1)).unwrap()
+    );
+
+    engine.resume_settlement(&mut ledger, |_, _| Ok(())).unwrap();
+    assert!(ledger.is_complete());
+
+    assert!(matches!(engine.auto_resolve("market-2", &bets), Err(MarketError::Oracle(_))));
+}
+
+#[test]
+fn test_manual_oracle_requires_outcome_to_be_set() {
+    let mut oracle = ManualOracle::new();
+    assert!(matches!(oracle.resolve("market-1"), Err(OracleError::Unresolved(_))));
+
+    oracle.set_outcome("market-1", SettlementOutcome::Void);
+    let engine = SettlementEngine::new(binary_config()).with_oracle(Box::new(oracle));
+    let ledger = engine.auto_resolve("market-1", &sample_bets()).unwrap();
+    assert_eq!(ledger.outcome, SettlementOutcome::Void);
+
+    assert!(SettlementEngine::new(binary_config()).auto_resolve("market-1", &sample_bets()).is_err());
+}
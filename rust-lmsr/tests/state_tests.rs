@@ -0,0 +1,56 @@
+use rust_decimal::prelude::*;
+use rust_lmsr::{Bet, MarketConfig, MarketState, MarketType, PredictionMarketEngine, ProbabilityEngine};
+
+#[test]
+fn test_exported_state_round_trips() {
+    let engine = PredictionMarketEngine::try_new(25.0, 3, MarketType::Categorical).unwrap();
+    let bets = vec![
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 2, amount: 12.5 },
+        Bet { option_id: 1, amount: 0.1 },
+    ];
+
+    let fixture = engine.export_state(&bets);
+    let restored = PredictionMarketEngine::from_state(&fixture)
+        .expect("Fixture should reconstruct an engine");
+    let state = MarketState::from_json(&fixture).expect("Fixture should parse");
+
+    let original = ProbabilityEngine::new(engine.config().clone())
+        .calculate_probabilities(&bets)
+        .unwrap();
+    let replayed = ProbabilityEngine::new(restored.config().clone())
+        .calculate_probabilities(&state.bets)
+        .unwrap();
+
+    assert_eq!(original, replayed);
+}
+
+#[test]
+fn test_malformed_state_is_rejected() {
+    assert!(PredictionMarketEngine::from_state("{\"config\": 1}").is_err());
+}
+
+#[test]
+fn test_partition_function_matches_hand_computed_value() {
+    let engine = ProbabilityEngine::new(MarketConfig::new(10.0, 2, MarketType::Binary));
+    let bets = vec![Bet { option_id: 0, amount: 10.0 }];
+
+    // q = (10, 0), b = 10: Z = e^1 + e^0
+    let partition = engine.partition_function(&bets).unwrap();
+    assert!((partition - (1f64.exp() + 1.0)).abs() < 1e-12);
+
+    let probabilities = engine.calculate_probabilities(&bets).unwrap();
+    for (p, term) in probabilities.iter().zip([1f64.exp(), 1.0]) {
+        assert!((p.to_f64().unwrap() - term / partition).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_exported_state_includes_partition_function() {
+    let engine = PredictionMarketEngine::try_new(10.0, 2, MarketType::Binary).unwrap();
+    let bets = vec![Bet { option_id: 0, amount: 10.0 }];
+
+    let state = MarketState::from_json(&engine.export_state(&bets)).unwrap();
+
+    assert!((state.partition_function.unwrap() - (1f64.exp() + 1.0)).abs() < 1e-12);
+}
@@ -1,8 +1,15 @@
 use wasm_bindgen_test::*;
-use rust_lmsr::{PredictionMarketEngine, Bet, MarketType};
+use rust_lmsr::{PredictionMarketEngine, Bet, MarketType, MarketMakingStrategy, MarketRiskProfile};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
+fn sample_bets() -> Vec<wasm_bindgen::JsValue> {
+    to_js_bets(&[
+        Bet { option_id: 0, amount: 50.0 },
+        Bet { option_id: 1, amount: 30.0 }
+    ])
+}
+
 #[wasm_bindgen_test]
 fn test_prediction_market_engine_creation() {
     let engine = PredictionMarketEngine::new(
@@ -11,8 +18,8 @@ fn test_prediction_market_engine_creation() {
         MarketType::Binary
     );
 
-    // Ensure the engine is created successfully
-    assert!(true, "PredictionMarketEngine should be created without errors");
+    assert!(engine.is_ok(), "PredictionMarketEngine should be created without errors");
+    assert!(PredictionMarketEngine::new(0.0, 2, MarketType::Binary).is_err(), "Zero liquidity should be rejected");
 }
 
 #[wasm_bindgen_test]
@@ -21,19 +28,14 @@ fn test_calculate_probabilities() {
         10.0,  // liquidity parameter
         2,     // number of outcomes
         MarketType::Binary
-    );
-
-    let bets = vec![
-        Bet { option_id: 0, amount: 50.0 },
-        Bet { option_id: 1, amount: 30.0 }
-    ];
+    ).unwrap();
 
-    let probabilities = engine.calculate_probabilities(&bets)
+    let probabilities = engine.calculate_probabilities(sample_bets(), None)
         .expect("Probabilities calculation should succeed");
 
     assert_eq!(probabilities.len(), 2, "Should return probabilities for all outcomes");
     probabilities.iter().for_each(|&p| {
-        assert!(p >= 0.0 && p <= 1.0, "Probabilities should be between 0 and 1");
+        assert!((0.0..=1.0).contains(&p), "Probabilities should be between 0 and 1");
     });
 }
 
@@ -43,17 +45,12 @@ fn test_calculate_market_price() {
         10.0,  // liquidity parameter
         2,     // number of outcomes
         MarketType::Binary
-    );
+    ).unwrap();
 
-    let bets = vec![
-        Bet { option_id: 0, amount: 50.0 },
-        Bet { option_id: 1, amount: 30.0 }
-    ];
-
-    let price = engine.calculate_price(&bets, 0)
+    let price = engine.calculate_price(sample_bets(), 0, None)
         .expect("Price calculation should succeed");
 
-    assert!(price >= 0.0 && price <= 1.0, "Price should be between 0 and 1");
+    assert!((0.0..=1.0).contains(&price), "Price should be between 0 and 1");
 }
 
 #[wasm_bindgen_test]
@@ -62,19 +59,15 @@ fn test_simulate_market_making() {
         10.0,  // liquidity parameter
         2,     // number of outcomes
         MarketType::Binary
-    );
+    ).unwrap();
 
-    let bets = vec![
-        Bet { option_id: 0, amount: 50.0 },
-        Bet { option_id: 1, amount: 30.0 }
-    ];
-
-    let strategy = engine.simulate_market_making(&bets)
-        .expect("Market making simulation should succeed");
+    let strategy: MarketMakingStrategy = serde_wasm_bindgen::from_value(
+        engine.simulate_market_making(sample_bets()).expect("Market making simulation should succeed")
+    ).unwrap();
 
-    assert!(!strategy.bid_prices.is_empty(), "Bid prices should not be empty");
-    assert!(!strategy.ask_prices.is_empty(), "Ask prices should not be empty");
-    assert!(strategy.spread >= 0.0, "Spread should be non-negative");
+    assert!(!strategy.bid_prices().is_empty(), "Bid prices should not be empty");
+    assert!(!strategy.ask_prices().is_empty(), "Ask prices should not be empty");
+    assert!(strategy.spread() >= 0.0, "Spread should be non-negative");
 }
 
 #[wasm_bindgen_test]
@@ -83,19 +76,15 @@ fn test_assess_market_risk() {
         10.0,  // liquidity parameter
         2,     // number of outcomes
         MarketType::Binary
-    );
+    ).unwrap();
 
-    let bets = vec![
-        Bet { option_id: 0, amount: 50.0 },
-        Bet { option_id: 1, amount: 30.0 }
-    ];
-
-    let risk_profile = engine.assess_market_risk(&bets)
-        .expect("Market risk assessment should succeed");
+    let risk_profile: MarketRiskProfile = serde_wasm_bindgen::from_value(
+        engine.assess_market_risk(sample_bets()).expect("Market risk assessment should succeed")
+    ).unwrap();
 
-    assert!(!risk_profile.probabilities.is_empty(), "Probabilities should not be empty");
-    assert!(risk_profile.entropy >= 0.0, "Entropy should be non-negative");
-    assert!(risk_profile.concentration >= 0.0, "Concentration should be non-negative");
+    assert!(!risk_profile.probabilities().is_empty(), "Probabilities should not be empty");
+    assert!(risk_profile.entropy() >= 0.0, "Entropy should be non-negative");
+    assert!(risk_profile.concentration() >= 0.0, "Concentration should be non-negative");
 }
 
 #[wasm_bindgen_test]
@@ -104,14 +93,15 @@ fn test_error_handling() {
         10.0,  // liquidity parameter
         2,     // number of outcomes
         MarketType::Binary
-    );
+    ).unwrap();
 
     // Test with invalid outcome index
-    let bets = vec![
-        Bet { option_id: 0, amount: 50.0 },
-        Bet { option_id: 1, amount: 30.0 }
-    ];
-
-    let price_result = engine.calculate_price(&bets, 5);
+    let price_result = engine.calculate_price(sample_bets(), 5, None);
     assert!(price_result.is_err(), "Should return an error for invalid outcome index");
-}
\ No newline at end of file
+}
+
+fn to_js_bets(bets: &[Bet]) -> Vec<wasm_bindgen::JsValue> {
+    bets.iter()
+        .map(|bet| serde_wasm_bindgen::to_value(bet).unwrap())
+        .collect()
+}
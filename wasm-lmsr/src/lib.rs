@@ -2,8 +2,9 @@ use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn calculate_lmsr_probabilities(liquidity_param: f64, num_outcomes: usize, bets: Vec<f64>) -> Vec<f64> {
-    // Simple LMSR probability calculation
-    let mut outcome_totals = vec![1.0f64; num_outcomes];
+    // LMSR prices p_i = exp(q_i / b) / Σ exp(q_j / b), where q_i is the
+    // volume bet on outcome i. Bets beyond `num_outcomes` are ignored.
+    let mut outcome_totals = vec![0.0f64; num_outcomes];
 
     for (i, &amount) in bets.iter().enumerate() {
         if i < num_outcomes {
@@ -11,15 +12,13 @@ pub fn calculate_lmsr_probabilities(liquidity_param: f64, num_outcomes: usize, b
         }
     }
 
+    // Exponentiate relative to the largest total so big markets don't overflow
     let max_total = outcome_totals.iter()
         .copied()
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(1.0);
-
-    let scale_factor = max_total / 10.0;
+        .fold(f64::NEG_INFINITY, f64::max);
 
     let exp_values: Vec<f64> = outcome_totals.iter()
-        .map(|&total| ((total / scale_factor) as f64).exp())
+        .map(|&total| ((total - max_total) / liquidity_param).exp())
         .collect();
 
     let sum_exp: f64 = exp_values.iter().sum();
@@ -0,0 +1,22 @@
+use wasm_lmsr::calculate_lmsr_probabilities;
+
+// The golden cases rust-lmsr's parity tests check its engines against, so
+// the two crates cannot drift apart without one of them failing
+include!("../../rust-lmsr/tests/fixtures/parity_cases.rs");
+
+const TOLERANCE: f64 = 1e-9;
+
+#[test]
+fn test_probabilities_match_shared_fixture() {
+    for (case, fixture) in PARITY_CASES.iter().enumerate() {
+        let actual = calculate_lmsr_probabilities(fixture.liquidity, fixture.volumes.len(), fixture.volumes.to_vec());
+
+        assert_eq!(actual.len(), fixture.probabilities.len());
+        for (outcome, (a, e)) in actual.iter().zip(fixture.probabilities).enumerate() {
+            assert!(
+                (a - e).abs() < TOLERANCE,
+                "Drifted in case {} outcome {}: {} vs golden {}", case, outcome, a, e
+            );
+        }
+    }
+}